@@ -1,6 +1,6 @@
 use std::env;
 
-use dacpac::{bacpac, simple::SimpleDacPacModel};
+use dacpac::bacpac;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -14,5 +14,7 @@ fn main() {
     //    println!("{:#?}", table.name);
     // }
 
-    let _table_data = bacpacc.read_data("dbo.Table".to_string()).unwrap();
+    for row in bacpacc.rows("dbo.Table").unwrap() {
+        println!("{:?}", row.unwrap().data);
+    }
 }
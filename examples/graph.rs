@@ -0,0 +1,29 @@
+use std::env;
+
+use dacpac::element::ElementGraph;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let fname = std::path::Path::new(&*args[1]);
+    let zipfile = std::fs::File::open(fname).unwrap();
+
+    let graph = ElementGraph::from_file(&zipfile).unwrap();
+
+    for table in graph.by_type("SqlTable") {
+        println!("{}", table.display_name());
+        for schema in graph.related(table, "Schema") {
+            println!("  schema: {}", schema.display_name());
+        }
+        for attached in &table.attached_annotations {
+            println!("  annotation: {:?}", graph.attached_annotation(attached));
+        }
+        for column in table.elements("Columns") {
+            println!("  {} ({})", column.display_name(), column.ty);
+        }
+    }
+
+    for e in graph.iter().filter(|e| e.ty == "SqlTypeSpecifier") {
+        println!("{:?}", e.reference("Type"));
+    }
+}
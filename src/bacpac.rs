@@ -1,10 +1,12 @@
-use std::io::Read;
-
 use crate::{
+    bcp::BcpReader,
+    name::ObjectName,
     simple::{self, SimpleDacPacModel},
     DacPacModel,
 };
-use anyhow::Result;
+use anyhow::{bail, Result};
+use std::io::{Read, Seek, SeekFrom, Take};
+use tracing::debug;
 
 #[derive(Debug)]
 pub struct BacPacModel {
@@ -18,10 +20,14 @@ pub struct TableData {
     pub rows: Vec<TableRowData>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TableColumnHeader {
     pub name: String,
     pub ty: simple::SimpleColumnType,
+    pub nullable: bool,
+    /// The collation of the column, or else of the database, which gives the
+    /// code page of `char`, `varchar` and `text` values
+    pub collation: Option<String>,
 }
 
 #[derive(Debug)]
@@ -29,6 +35,43 @@ pub struct TableRowData {
     pub data: Vec<simple::SimpleColumnValue>,
 }
 
+impl TableColumnHeader {
+    /// Returns the headers of the columns of `table` that have data in the export
+    pub(crate) fn for_table(
+        table: &simple::SimpleTable,
+        database_collation: Option<&str>,
+    ) -> Result<Vec<TableColumnHeader>> {
+        let mut headers: Vec<TableColumnHeader> = vec![];
+        for row in table.columns.iter() {
+            // Non-persisted computed columns have no data in the export
+            if row.computed.as_ref().is_some_and(|c| !c.persisted) {
+                continue;
+            }
+            let ty = match &row.ty {
+                Some(ty) => ty.clone(),
+                None => {
+                    return Err(anyhow::anyhow!(
+                        "Column {} of {} has no known type",
+                        row.name,
+                        table.name
+                    ))
+                }
+            };
+            headers.push(TableColumnHeader {
+                name: row.name.clone(),
+                ty,
+                nullable: row.nullable,
+                collation: row
+                    .collation
+                    .as_deref()
+                    .or(database_collation)
+                    .map(str::to_string),
+            });
+        }
+        Ok(headers)
+    }
+}
+
 impl BacPacModel {
     pub fn from_file(file: std::fs::File) -> Result<BacPacModel> {
         let dc = DacPacModel::from_file(&file)?;
@@ -37,95 +80,276 @@ impl BacPacModel {
             simple_dacpac: SimpleDacPacModel::from(&dc),
         })
     }
+    /// Reads all the rows of a table into memory. See [`BacPacModel::rows`]
+    /// for large tables.
     pub fn read_data(&self, table_name: String) -> Result<TableData> {
-        let table = self
-            .simple_dacpac
-            .tables
-            .iter()
-            .find(|t| t.name == table_name);
+        let rows = self.rows(&table_name)?;
+        let headers = rows.headers.clone();
+        let rows = rows.collect::<Result<Vec<TableRowData>>>()?;
+
+        Ok(TableData { headers, rows })
+    }
+
+    /// Returns an iterator decoding the rows of a table lazily. `table_name`
+    /// is `schema.table`, with or without brackets.
+    ///
+    /// The `Data/<table>/*.BCP` entries are decompressed as the rows are read,
+    /// so memory use stays flat whatever the size of the table.
+    pub fn rows(&self, table_name: &str) -> Result<TableRows<'_>> {
+        let name: ObjectName = table_name.parse()?;
+        let table = self.simple_dacpac.tables.iter().find(|t| t.name == name);
 
         let table = match table {
             Some(t) => t,
             None => return Err(anyhow::anyhow!("Table {table_name} not found")),
         };
 
-        let mut headers: Vec<TableColumnHeader> = vec![];
-        for row in table.columns.iter() {
-            headers.push(TableColumnHeader {
-                name: row.name.clone(),
-                ty: row.ty.clone(),
-            });
-        }
-        println!("Headers: {:#?}", headers);
+        let database_collation = self
+            .simple_dacpac
+            .options
+            .as_ref()
+            .and_then(|o| o.collation.as_deref());
+        let headers = TableColumnHeader::for_table(table, database_collation)?;
+        debug!("Headers: {:#?}", headers);
 
-        let archive = zip::ZipArchive::new(&self.file).unwrap();
+        let archive = zip::ZipArchive::new(&self.file)?;
 
-        let folder_prefix = format!("Data/{}", table_name);
+        let folder_prefix = format!(
+            "Data/{}.{}/",
+            table.name.schema.as_deref().unwrap_or_default(),
+            table.name.name
+        );
 
         let mut bcp_files: Vec<String> = vec![];
         for file in archive.file_names() {
-            println!("File: {:#?}", file);
-            println!("Starts with: {:#?}", file.starts_with(&folder_prefix));
-            println!("Ends with: {:#?}", file.ends_with(".BCP"));
             if file.starts_with(&folder_prefix) && file.ends_with(".BCP") {
                 bcp_files.push(file.to_string());
             }
         }
         bcp_files.sort();
 
-        println!("Files: {:#?}", bcp_files);
+        debug!("Files: {:#?}", bcp_files);
+
+        Ok(TableRows {
+            file: &self.file,
+            archive,
+            headers,
+            bcp_files: bcp_files.into_iter(),
+            current: None,
+        })
+    }
+}
+
+/// Lazy iterator over the rows of a table, returned by [`BacPacModel::rows`]
+pub struct TableRows<'a> {
+    file: &'a std::fs::File,
+    archive: zip::ZipArchive<&'a std::fs::File>,
+    pub headers: Vec<TableColumnHeader>,
+    bcp_files: std::vec::IntoIter<String>,
+    current: Option<BcpReader<EntryReader<'a>>>,
+}
+
+impl<'a> TableRows<'a> {
+    /// Starts streaming the next BCP entry. Returns `false` once all of them were read.
+    fn open_next_file(&mut self) -> Result<bool> {
+        let file = match self.bcp_files.next() {
+            Some(f) => f,
+            None => return Ok(false),
+        };
+
+        let bcp_file = self.archive.by_name(&file)?;
+        debug!("File: {} ({} bytes)", file, bcp_file.size());
+
+        let compressed = FileSlice {
+            file: self.file,
+            pos: bcp_file.data_start(),
+        }
+        .take(bcp_file.compressed_size());
+        let data = match bcp_file.compression() {
+            zip::CompressionMethod::Stored => EntryData::Stored(compressed),
+            zip::CompressionMethod::Deflated => {
+                EntryData::Deflated(flate2::read::DeflateDecoder::new(compressed))
+            }
+            method => bail!("Unsupported compression method {method} of {file}"),
+        };
+        let reader = EntryReader {
+            name: file,
+            data,
+            hasher: crc32fast::Hasher::new(),
+            expected_crc: bcp_file.crc32(),
+        };
+        self.current = Some(BcpReader::new(reader, &self.headers));
+
+        Ok(true)
+    }
+
+    /// Ends the iteration: nothing can be decoded past a broken row or entry
+    fn stop(&mut self) {
+        self.current = None;
+        self.bcp_files = Vec::new().into_iter();
+    }
+}
+
+/// Reads a file from a given offset, seeking before every read so that
+/// several readers can share the file
+struct FileSlice<'a> {
+    file: &'a std::fs::File,
+    pos: u64,
+}
 
-        let rows = Self::parse_bcp(&headers, &bcp_files, &archive);
+impl Read for FileSlice<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut file = self.file;
+        file.seek(SeekFrom::Start(self.pos))?;
+        let n = file.read(buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+enum EntryData<'a> {
+    Stored(Take<FileSlice<'a>>),
+    Deflated(flate2::read::DeflateDecoder<Take<FileSlice<'a>>>),
+}
 
-        Err(anyhow::anyhow!("Not implemented"))
+/// Decompresses a zip entry as it is read, checking its CRC at the end
+struct EntryReader<'a> {
+    name: String,
+    data: EntryData<'a>,
+    hasher: crc32fast::Hasher,
+    expected_crc: u32,
+}
+
+impl Read for EntryReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = match &mut self.data {
+            EntryData::Stored(r) => r.read(buf)?,
+            EntryData::Deflated(r) => r.read(buf)?,
+        };
+        if n > 0 {
+            self.hasher.update(&buf[..n]);
+        } else if !buf.is_empty() && self.hasher.clone().finalize() != self.expected_crc {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("CRC mismatch in {}", self.name),
+            ));
+        }
+        Ok(n)
     }
+}
+
+impl Iterator for TableRows<'_> {
+    type Item = Result<TableRowData>;
 
-    fn parse_bcp(
-        headers: &Vec<TableColumnHeader>,
-        bcp_files: &Vec<String>,
-        archive: &zip::ZipArchive<std::fs::File>,
-    ) -> Vec<TableRowData> {
-        let mut rows: Vec<TableRowData> = vec![];
-
-        for file in bcp_files {
-            let mut bcp_file = archive.by_name(file).unwrap();
-            let b = bcp_file.read(buf)
-            println!("File: {:#?}", b);
-
-            let mut contents = String::new();
-            bcp_file.read_to_string(&mut contents).unwrap();
-
-            let lines = contents.lines();
-            for line in lines {
-                let mut row_data: Vec<simple::SimpleColumnValue> = vec![];
-                let mut line = line.to_string();
-                line.pop(); // Remove the last character which is a delimiter
-                let mut line = line.split("\t");
-                for header in headers {
-                    let value = line.next().unwrap();
-                    let value = match header.ty {
-                        simple::SimpleColumnType::Int => {
-                            simple::SimpleColumnValue::Int(value.parse().unwrap())
-                        }
-                        simple::SimpleColumnType::BigInt => {
-                            simple::SimpleColumnValue::BigInt(value.parse().unwrap())
-                        }
-                        simple::SimpleColumnType::Nvarchar(_) => {
-                            simple::SimpleColumnValue::Nvarchar(value.to_string())
-                        }
-                        simple::SimpleColumnType::Varchar(_) => {
-                            simple::SimpleColumnValue::Varchar(value.to_string())
-                        }
-                        simple::SimpleColumnType::DateTime2(_) => {
-                            simple::SimpleColumnValue::DateTime2(value.to_string())
-                        }
-                    };
-                    row_data.push(value);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(reader) = &mut self.current {
+                match reader.next() {
+                    Some(Ok(row)) => return Some(Ok(row)),
+                    Some(Err(e)) => {
+                        self.stop();
+                        return Some(Err(e));
+                    }
+                    None => self.current = None,
                 }
-                rows.push(TableRowData { data: row_data });
             }
+            match self.open_next_file() {
+                Ok(true) => continue,
+                Ok(false) => return None,
+                Err(e) => {
+                    self.stop();
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simple::SimpleColumnValue as V;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    const MODEL: &str = r#"<DataSchemaModel><Model>
+        <Element Type="SqlTable" Name="[dbo].[T]">
+          <Relationship Name="Columns"><Entry><Element Type="SqlSimpleColumn" Name="[dbo].[T].[Id]">
+            <Property Name="IsNullable" Value="False" />
+            <Relationship Name="TypeSpecifier"><Entry><Element Type="SqlTypeSpecifier">
+              <Relationship Name="Type"><Entry><References ExternalSource="BuiltIns" Name="[int]" /></Entry></Relationship>
+            </Element></Entry></Relationship>
+          </Element></Entry></Relationship>
+          <Relationship Name="Schema"><Entry><References ExternalSource="BuiltIns" Name="[dbo]" /></Entry></Relationship>
+        </Element>
+        </Model></DataSchemaModel>"#;
+
+    /// Writes a bacpac holding `entries` next to the model of `[dbo].[T]`
+    fn bacpac(test: &str, entries: &[(&str, zip::CompressionMethod, Vec<u8>)]) -> BacPacModel {
+        let path =
+            std::env::temp_dir().join(format!("dacpac-{}-{test}.bacpac", std::process::id()));
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        zip.start_file("model.xml", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(MODEL.as_bytes()).unwrap();
+        for (name, method, data) in entries {
+            let options = SimpleFileOptions::default().compression_method(*method);
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(data).unwrap();
         }
+        zip.finish().unwrap();
+
+        let model = BacPacModel::from_file(std::fs::File::open(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        model
+    }
+
+    fn ints(values: &[i32]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn rows_are_streamed_from_every_entry_in_order() {
+        use zip::CompressionMethod::{Deflated, Stored};
+        let model = bacpac(
+            "order",
+            &[
+                ("Data/dbo.T/TableData-002-00000.BCP", Deflated, ints(&[5])),
+                ("Data/dbo.T/TableData-000-00000.BCP", Stored, ints(&[1, 2])),
+                (
+                    "Data/dbo.T/TableData-001-00000.BCP",
+                    Deflated,
+                    ints(&[3, 4]),
+                ),
+                ("Data/dbo.U/TableData-000-00000.BCP", Stored, ints(&[9])),
+            ],
+        );
+
+        let rows = model
+            .rows("[dbo].[T]")
+            .unwrap()
+            .map(|row| row.unwrap().data)
+            .collect::<Vec<_>>();
+        let expected = (1..=5).map(|i| vec![V::Int(i)]).collect::<Vec<_>>();
+        assert_eq!(rows, expected);
+    }
+
+    #[test]
+    fn rows_stop_after_an_error() {
+        use zip::CompressionMethod::Deflated;
+        let mut truncated = ints(&[1]);
+        truncated.extend_from_slice(&[2, 0]);
+        let model = bacpac(
+            "error",
+            &[
+                ("Data/dbo.T/TableData-000-00000.BCP", Deflated, truncated),
+                ("Data/dbo.T/TableData-001-00000.BCP", Deflated, ints(&[3])),
+            ],
+        );
 
-        rows
+        let mut rows = model.rows("[dbo].[T]").unwrap();
+        assert_eq!(rows.next().unwrap().unwrap().data, vec![V::Int(1)]);
+        assert!(rows.next().unwrap().is_err());
+        assert!(rows.next().is_none());
     }
 }
@@ -0,0 +1,802 @@
+//! Decoder for the SQL Server native BCP format used by the `Data/<table>/*.BCP`
+//! entries of a bacpac.
+//!
+//! Every row is the concatenation of its column values, in column order. Each
+//! value is preceded by a little-endian length prefix whose size depends on the
+//! column type:
+//!  - fixed-size types have no prefix, or a 1 byte prefix when nullable
+//!  - `decimal`, `numeric`, the `date`/`time` family, `uniqueidentifier` and
+//!    `rowversion` always have a 1 byte prefix
+//!  - `char`, `varchar`, `nchar`, `nvarchar`, `binary` and `varbinary` have a
+//!    2 byte prefix
+//!  - `text`, `ntext` and `image` have a 4 byte prefix
+//!  - `(max)` types, `xml`, `sql_variant` and CLR types have an 8 byte prefix
+//!
+//! A prefix with all bits set marks a NULL value, which is decoded as
+//! [`SimpleColumnValue::Null`] and rejected in non-nullable columns.
+
+use std::io::{ErrorKind, Read};
+
+use anyhow::{bail, Context, Result};
+
+use crate::bacpac::{TableColumnHeader, TableRowData};
+use crate::decimal::Decimal;
+use crate::simple::{SimpleColumnLength, SimpleColumnType, SimpleColumnValue};
+use crate::temporal::{Date, DateTime, DateTimeOffset, Time};
+
+/// Iterator over the rows of a single native BCP stream
+pub struct BcpReader<R> {
+    reader: R,
+    headers: Vec<TableColumnHeader>,
+}
+
+impl<R: Read> BcpReader<R> {
+    pub fn new(reader: R, headers: &[TableColumnHeader]) -> Self {
+        BcpReader {
+            reader,
+            headers: headers.to_vec(),
+        }
+    }
+
+    /// Decodes the next row, or returns `None` at the end of the stream
+    pub fn read_row(&mut self) -> Result<Option<TableRowData>> {
+        if self.headers.is_empty() {
+            bail!("Can't decode BCP rows without columns");
+        }
+        let mut data: Vec<SimpleColumnValue> = Vec::with_capacity(self.headers.len());
+
+        for (i, header) in self.headers.iter().enumerate() {
+            // The stream may only end cleanly before the first byte of a row
+            let at_row_start = i == 0;
+            let prefix_len = prefix_len(header);
+
+            let len = match prefix_len {
+                0 => fixed_len(&header.ty),
+                n => {
+                    let mut prefix = [0u8; 8];
+                    if !fill(&mut self.reader, &mut prefix[..n], at_row_start)? {
+                        return Ok(None);
+                    }
+                    if prefix[..n].iter().all(|b| *b == 0xFF) {
+                        if !header.nullable {
+                            bail!(
+                                "Data integrity error: NULL value in non-nullable column {}",
+                                header.name
+                            );
+                        }
+                        data.push(SimpleColumnValue::Null);
+                        continue;
+                    }
+                    u64::from_le_bytes(prefix) as usize
+                }
+            };
+
+            if let Some(max) = max_len(&header.ty) {
+                if len > max {
+                    bail!(
+                        "Value of {len} bytes in column {}, which holds at most {max}",
+                        header.name
+                    );
+                }
+            }
+
+            // Read through `take` so that a corrupt prefix can't size the buffer
+            let mut buf = Vec::new();
+            (&mut self.reader).take(len as u64).read_to_end(&mut buf)?;
+            if buf.len() < len {
+                if buf.is_empty() && at_row_start && prefix_len == 0 {
+                    return Ok(None);
+                }
+                bail!("Truncated BCP row");
+            }
+
+            data.push(
+                decode_value(header, &buf).with_context(|| format!("column {}", header.name))?,
+            );
+        }
+
+        Ok(Some(TableRowData { data }))
+    }
+}
+
+impl<R: Read> Iterator for BcpReader<R> {
+    type Item = Result<TableRowData>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_row().transpose()
+    }
+}
+
+/// Size in bytes of the length prefix written before a value of this column
+fn prefix_len(header: &TableColumnHeader) -> usize {
+    use SimpleColumnLength as L;
+    use SimpleColumnType as T;
+
+    match header.ty {
+        T::Bit
+        | T::TinyInt
+        | T::SmallInt
+        | T::Int
+        | T::BigInt
+        | T::Money
+        | T::SmallMoney
+        | T::Float(_)
+        | T::Real
+        | T::DateTime
+        | T::SmallDateTime => usize::from(header.nullable),
+        T::Decimal(..)
+        | T::Numeric(..)
+        | T::Date
+        | T::Time(_)
+        | T::DateTime2(_)
+        | T::DateTimeOffset(_)
+        | T::UniqueIdentifier
+        | T::RowVersion => 1,
+        T::Varchar(L::Max) | T::Nvarchar(L::Max) | T::Varbinary(L::Max) => 8,
+        T::Char(_) | T::Varchar(_) | T::Nchar(_) | T::Nvarchar(_) => 2,
+        T::Binary(_) | T::Varbinary(_) => 2,
+        T::Text | T::Ntext | T::Image => 4,
+        T::Xml | T::SqlVariant | T::HierarchyId | T::Geography | T::Geometry | T::Other(_) => 8,
+    }
+}
+
+/// Largest value in bytes the declared length of a column allows, if bounded
+fn max_len(ty: &SimpleColumnType) -> Option<usize> {
+    use SimpleColumnLength as L;
+    use SimpleColumnType as T;
+
+    match ty {
+        T::Char(L::Bounded(n))
+        | T::Varchar(L::Bounded(n))
+        | T::Binary(L::Bounded(n))
+        | T::Varbinary(L::Bounded(n)) => usize::try_from(*n).ok(),
+        T::Nchar(L::Bounded(n)) | T::Nvarchar(L::Bounded(n)) => {
+            usize::try_from(*n).ok().map(|n| n * 2)
+        }
+        _ => None,
+    }
+}
+
+/// Size in bytes of a value of a fixed-size type
+fn fixed_len(ty: &SimpleColumnType) -> usize {
+    use SimpleColumnType as T;
+
+    match ty {
+        T::Bit | T::TinyInt => 1,
+        T::SmallInt => 2,
+        T::Int | T::SmallMoney | T::Real | T::SmallDateTime => 4,
+        T::BigInt | T::Money | T::DateTime => 8,
+        // float(1) to float(24) are stored as real
+        T::Float(bits) if (1..=24).contains(bits) => 4,
+        T::Float(_) => 8,
+        _ => unreachable!("{ty:?} values are always length prefixed"),
+    }
+}
+
+/// Fills `buf` completely. Returns `false` if the stream was already at its
+/// end and `eof_allowed` is set, errors on any other short read.
+fn fill<R: Read>(reader: &mut R, buf: &mut [u8], eof_allowed: bool) -> Result<bool> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]) {
+            Ok(0) if read == 0 && eof_allowed => return Ok(false),
+            Ok(0) => bail!("Truncated BCP row"),
+            Ok(n) => read += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(true)
+}
+
+fn decode_value(header: &TableColumnHeader, buf: &[u8]) -> Result<SimpleColumnValue> {
+    use SimpleColumnType as T;
+    use SimpleColumnValue as V;
+
+    let value = match header.ty {
+        T::Bit => V::Bit(u8::from_le_bytes(buf.try_into()?) != 0),
+        T::TinyInt => V::TinyInt(u8::from_le_bytes(buf.try_into()?)),
+        T::SmallInt => V::SmallInt(i16::from_le_bytes(buf.try_into()?)),
+        T::Int => V::Int(i32::from_le_bytes(buf.try_into()?)),
+        T::BigInt => V::BigInt(i64::from_le_bytes(buf.try_into()?)),
+        T::Decimal(..) => V::Decimal(decode_decimal(buf)?),
+        T::Numeric(..) => V::Numeric(decode_decimal(buf)?),
+        T::Money => V::Money(decode_money(buf)?),
+        T::SmallMoney => V::SmallMoney(decode_smallmoney(buf)?),
+        T::Float(_) if buf.len() == 4 => V::Float(f32::from_le_bytes(buf.try_into()?).into()),
+        T::Float(_) => V::Float(f64::from_le_bytes(buf.try_into()?)),
+        T::Real => V::Real(f32::from_le_bytes(buf.try_into()?)),
+        T::Date => V::Date(decode_date(buf)?),
+        T::Time(scale) => V::Time(decode_time(buf, scale)?),
+        T::DateTime => V::DateTime(decode_datetime(buf)?),
+        T::SmallDateTime => V::SmallDateTime(decode_smalldatetime(buf)?),
+        T::DateTime2(scale) => V::DateTime2(decode_datetime2(buf, scale)?),
+        T::DateTimeOffset(scale) => V::DateTimeOffset(decode_datetimeoffset(buf, scale)?),
+        T::Char(_) => V::Char(decode_ansi(buf, header)?),
+        T::Varchar(_) => V::Varchar(decode_ansi(buf, header)?),
+        T::Text => V::Text(decode_ansi(buf, header)?),
+        T::Nchar(_) => V::Nchar(decode_utf16(buf)?),
+        T::Nvarchar(_) => V::Nvarchar(decode_utf16(buf)?),
+        T::Ntext => V::Ntext(decode_utf16(buf)?),
+        T::Binary(_) => V::Binary(buf.to_vec()),
+        T::Varbinary(_) => V::Varbinary(buf.to_vec()),
+        T::Image => V::Image(buf.to_vec()),
+        T::UniqueIdentifier => V::UniqueIdentifier(decode_guid(buf)?),
+        T::Xml => V::Xml(
+            decode_utf16(buf)?
+                .trim_start_matches('\u{feff}')
+                .to_string(),
+        ),
+        T::SqlVariant => V::SqlVariant(buf.to_vec()),
+        T::HierarchyId => V::HierarchyId(buf.to_vec()),
+        T::Geography => V::Geography(buf.to_vec()),
+        T::Geometry => V::Geometry(buf.to_vec()),
+        T::Other(_) => V::Other(buf.to_vec()),
+        T::RowVersion => V::RowVersion(buf.to_vec()),
+    };
+    Ok(value)
+}
+
+fn decode_utf16(buf: &[u8]) -> Result<String> {
+    if !buf.len().is_multiple_of(2) {
+        bail!("Odd byte count ({}) in UTF-16 value", buf.len());
+    }
+    let units: Vec<u16> = buf
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    Ok(String::from_utf16(&units)?)
+}
+
+/// Decodes a single byte string in the code page of the column collation.
+///
+/// UTF-8 collations and the Latin1 collations using code page 1252, SQL
+/// Server's default, are supported. Values of other code pages only decode
+/// when they are plain ASCII, which all of them share.
+fn decode_ansi(buf: &[u8], header: &TableColumnHeader) -> Result<String> {
+    // Without a collation in the model, the server default applies
+    let collation = header
+        .collation
+        .as_deref()
+        .unwrap_or("SQL_Latin1_General_CP1_CI_AS");
+
+    if collation.to_ascii_uppercase().contains("_UTF8") {
+        return Ok(String::from_utf8(buf.to_vec())?);
+    }
+    if is_cp1252(collation) {
+        return Ok(buf.iter().map(|b| cp1252_char(*b)).collect());
+    }
+    if buf.is_ascii() {
+        return Ok(buf.iter().map(|b| char::from(*b)).collect());
+    }
+    bail!(
+        "Can't decode non-ASCII value of column {}: the code page of collation {collation} isn't supported",
+        header.name
+    )
+}
+
+/// Whether a collation stores single byte strings in code page 1252
+fn is_cp1252(collation: &str) -> bool {
+    const WINDOWS_PREFIXES: &[&str] = &[
+        "LATIN1_GENERAL_",
+        "DANISH_",
+        "FINNISH_SWEDISH_",
+        "FRENCH_",
+        "GERMAN_PHONEBOOK_",
+        "ICELANDIC_",
+        "MODERN_SPANISH_",
+        "TRADITIONAL_SPANISH_",
+        "MEXICAN_TRADITIONAL_SPANISH_",
+        "NORWEGIAN_",
+        "SAMI_",
+    ];
+    let collation = collation.to_ascii_uppercase();
+    if collation.starts_with("SQL_") {
+        // e.g. SQL_Latin1_General_CP1_CI_AS, but not ..._CP850_... or ..._CP1250_...
+        return collation.contains("_CP1_");
+    }
+    WINDOWS_PREFIXES.iter().any(|p| collation.starts_with(p))
+}
+
+/// Maps a code page 1252 byte to its character. Only 0x80 to 0x9F differ
+/// from Latin-1, the five bytes undefined there being kept as C1 controls.
+fn cp1252_char(b: u8) -> char {
+    const HIGH: [char; 32] = [
+        '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}',
+        '\u{2021}', '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}',
+        '\u{8F}', '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}',
+        '\u{2014}', '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}',
+        '\u{178}',
+    ];
+    match b {
+        0x80..=0x9F => HIGH[usize::from(b - 0x80)],
+        _ => char::from(b),
+    }
+}
+
+/// Reads a little-endian unsigned integer of up to 8 bytes
+fn read_uint(buf: &[u8]) -> Result<u64> {
+    if buf.len() > 8 {
+        bail!("Integer field too long ({} bytes)", buf.len());
+    }
+    let mut bytes = [0u8; 8];
+    bytes[..buf.len()].copy_from_slice(buf);
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_u24(buf: &[u8]) -> Result<u32> {
+    if buf.len() != 3 {
+        bail!("Invalid date length {}", buf.len());
+    }
+    Ok(u32::from_le_bytes([buf[0], buf[1], buf[2], 0]))
+}
+
+/// Decodes a `decimal`/`numeric`: precision, scale and sign bytes (1 for
+/// positive), followed by the little-endian magnitude.
+fn decode_decimal(buf: &[u8]) -> Result<Decimal> {
+    if buf.len() < 4 || buf.len() > 19 {
+        bail!("Invalid decimal length {}", buf.len());
+    }
+    let scale = buf[1];
+    let positive = buf[2] == 1;
+
+    let mut magnitude = [0u8; 16];
+    magnitude[..buf.len() - 3].copy_from_slice(&buf[3..]);
+    let magnitude = i128::try_from(u128::from_le_bytes(magnitude))?;
+
+    let mantissa = if positive { magnitude } else { -magnitude };
+    Ok(Decimal::new(mantissa, scale))
+}
+
+/// Decodes a `money`: the high then the low 32 bits of the amount in 1/10000
+fn decode_money(buf: &[u8]) -> Result<Decimal> {
+    if buf.len() != 8 {
+        bail!("Invalid money length {}", buf.len());
+    }
+    let high = i32::from_le_bytes(buf[..4].try_into()?);
+    let low = u32::from_le_bytes(buf[4..].try_into()?);
+    let amount = (i64::from(high) << 32) | i64::from(low);
+    Ok(Decimal::new(amount.into(), 4))
+}
+
+/// Decodes a `smallmoney`: the amount in 1/10000
+fn decode_smallmoney(buf: &[u8]) -> Result<Decimal> {
+    let amount = i32::from_le_bytes(buf.try_into()?);
+    Ok(Decimal::new(amount.into(), 4))
+}
+
+/// Decodes a `uniqueidentifier`, whose first three groups are little-endian
+fn decode_guid(buf: &[u8]) -> Result<String> {
+    if buf.len() != 16 {
+        bail!("Invalid uniqueidentifier length {}", buf.len());
+    }
+    Ok(format!(
+        "{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{}",
+        u32::from_le_bytes(buf[0..4].try_into()?),
+        u16::from_le_bytes(buf[4..6].try_into()?),
+        u16::from_le_bytes(buf[6..8].try_into()?),
+        buf[8],
+        buf[9],
+        buf[10..]
+            .iter()
+            .map(|b| format!("{b:02X}"))
+            .collect::<String>()
+    ))
+}
+
+/// Decodes a `date`: the number of days since 0001-01-01 (3 bytes)
+fn decode_date(buf: &[u8]) -> Result<Date> {
+    Ok(Date::from_days(read_u24(buf)?))
+}
+
+/// Decodes a `time(scale)`: the time of day in 10^-scale seconds (3 to 5 bytes)
+fn decode_time(buf: &[u8], scale: i8) -> Result<Time> {
+    time_from_ticks(read_uint(buf)?, time_scale(buf.len(), scale)?)
+}
+
+/// Returns the scale of a time value stored on `len` bytes: the column scale
+/// when its values have that length, else the largest scale that does
+fn time_scale(len: usize, scale: i8) -> Result<i8> {
+    let scales = match len {
+        3 => 0..=2,
+        4 => 3..=4,
+        5 => 5..=7,
+        _ => bail!("Invalid time length {len}"),
+    };
+    if scales.contains(&scale) {
+        Ok(scale)
+    } else {
+        Ok(*scales.end())
+    }
+}
+
+/// Decodes a `datetime`: days since 1900-01-01, then 1/300 seconds since midnight
+fn decode_datetime(buf: &[u8]) -> Result<DateTime> {
+    if buf.len() != 8 {
+        bail!("Invalid datetime length {}", buf.len());
+    }
+    let days = i32::from_le_bytes(buf[..4].try_into()?);
+    let ticks = u32::from_le_bytes(buf[4..].try_into()?);
+
+    // Rounded to the millisecond, as SQL Server displays it
+    let millis = (u64::from(ticks) * 10 + 1) / 3;
+    Ok(DateTime {
+        date: Date::from_days(u32::try_from(i64::from(days) + DAYS_TO_1900)?),
+        time: time_from_ticks(millis, 3)?,
+    })
+}
+
+/// Decodes a `smalldatetime`: days since 1900-01-01, then minutes since midnight
+fn decode_smalldatetime(buf: &[u8]) -> Result<DateTime> {
+    if buf.len() != 4 {
+        bail!("Invalid smalldatetime length {}", buf.len());
+    }
+    let days = u16::from_le_bytes(buf[..2].try_into()?);
+    let minutes = u16::from_le_bytes(buf[2..].try_into()?);
+
+    Ok(DateTime {
+        date: Date::from_days(u32::try_from(i64::from(days) + DAYS_TO_1900)?),
+        time: time_from_ticks(u64::from(minutes) * 60, 0)?,
+    })
+}
+
+/// Decodes a `datetime2(scale)`: a `time(scale)` followed by a `date`
+fn decode_datetime2(buf: &[u8], scale: i8) -> Result<DateTime> {
+    if !(6..=8).contains(&buf.len()) {
+        bail!("Invalid datetime2 length {}", buf.len());
+    }
+    let (time, date) = buf.split_at(buf.len() - 3);
+
+    Ok(DateTime {
+        date: decode_date(date)?,
+        time: decode_time(time, scale)?,
+    })
+}
+
+/// Decodes a `datetimeoffset(scale)`: a `datetime2(scale)` in UTC followed
+/// by the offset in minutes (2 bytes).
+fn decode_datetimeoffset(buf: &[u8], scale: i8) -> Result<DateTimeOffset> {
+    if !(8..=10).contains(&buf.len()) {
+        bail!("Invalid datetimeoffset length {}", buf.len());
+    }
+    let (datetime, offset) = buf.split_at(buf.len() - 2);
+    let (time, date) = datetime.split_at(datetime.len() - 3);
+    let offset = i16::from_le_bytes(offset.try_into()?);
+    let scale = time_scale(time.len(), scale)?;
+
+    // Shift the UTC value to the local time the offset refers to
+    let units_per_minute = 60 * i128::from(units_per_second(scale)?);
+    let units_per_day = 24 * 60 * units_per_minute;
+    let local = i128::from(read_u24(date)?) * units_per_day
+        + i128::from(read_uint(time)?)
+        + i128::from(offset) * units_per_minute;
+
+    Ok(DateTimeOffset {
+        local: DateTime {
+            date: Date::from_days(u32::try_from(local.div_euclid(units_per_day))?),
+            time: time_from_ticks(u64::try_from(local.rem_euclid(units_per_day))?, scale)?,
+        },
+        offset_minutes: offset,
+    })
+}
+
+/// Number of days between 0001-01-01 and 1900-01-01
+const DAYS_TO_1900: i64 = 693_595;
+
+fn units_per_second(scale: i8) -> Result<u64> {
+    match u32::try_from(scale) {
+        Ok(scale) if scale <= 7 => Ok(10u64.pow(scale)),
+        _ => bail!("Invalid fractional seconds scale {scale}"),
+    }
+}
+
+/// Builds a time from a number of 10^-scale seconds since midnight
+fn time_from_ticks(ticks: u64, scale: i8) -> Result<Time> {
+    if ticks >= 86_400 * units_per_second(scale)? {
+        bail!("Time of day out of range: {ticks} (scale {scale})");
+    }
+    Ok(Time::from_ticks(ticks, scale as u8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simple::SimpleColumnValue as V;
+    use crate::simple::SimpleDacPacModel;
+    use SimpleColumnLength as L;
+    use SimpleColumnType as T;
+
+    fn header(ty: SimpleColumnType, nullable: bool) -> TableColumnHeader {
+        TableColumnHeader {
+            name: "c".to_string(),
+            ty,
+            nullable,
+            collation: None,
+        }
+    }
+
+    fn rows(headers: &[TableColumnHeader], bytes: &[u8]) -> Result<Vec<Vec<V>>> {
+        BcpReader::new(bytes, headers)
+            .map(|row| row.map(|r| r.data))
+            .collect()
+    }
+
+    fn single(ty: SimpleColumnType, nullable: bool, bytes: &[u8]) -> V {
+        let mut rows = rows(&[header(ty, nullable)], bytes).unwrap();
+        assert_eq!(rows.len(), 1);
+        rows.remove(0).remove(0)
+    }
+
+    #[test]
+    fn fixed_size_values_have_no_prefix_unless_nullable() {
+        assert_eq!(single(T::Int, false, &[1, 0, 0, 0]), V::Int(1));
+        assert_eq!(single(T::Int, true, &[4, 1, 0, 0, 0]), V::Int(1));
+        assert_eq!(single(T::Int, true, &[0xFF]), V::Null);
+        assert_eq!(single(T::Bit, false, &[1]), V::Bit(true));
+        assert_eq!(
+            single(T::Float(24), false, &1.5f32.to_le_bytes()),
+            V::Float(1.5)
+        );
+        assert_eq!(
+            single(T::Float(53), false, &1.5f64.to_le_bytes()),
+            V::Float(1.5)
+        );
+    }
+
+    #[test]
+    fn two_byte_prefix() {
+        assert_eq!(
+            single(T::Varchar(L::Bounded(10)), true, &[2, 0, b'a', b'b']),
+            V::Varchar("ab".to_string())
+        );
+        assert_eq!(
+            single(T::Nvarchar(L::Bounded(10)), true, &[4, 0, b'a', 0, b'b', 0]),
+            V::Nvarchar("ab".to_string())
+        );
+        assert_eq!(
+            single(T::Varchar(L::Bounded(10)), true, &[0xFF, 0xFF]),
+            V::Null
+        );
+        assert_eq!(
+            single(T::Varbinary(L::Bounded(10)), true, &[0, 0]),
+            V::Varbinary(vec![])
+        );
+    }
+
+    #[test]
+    fn four_byte_prefix() {
+        assert_eq!(
+            single(T::Image, true, &[2, 0, 0, 0, 1, 2]),
+            V::Image(vec![1, 2])
+        );
+        assert_eq!(single(T::Ntext, true, &[0xFF; 4]), V::Null);
+    }
+
+    #[test]
+    fn eight_byte_prefix() {
+        assert_eq!(
+            single(
+                T::Nvarchar(L::Max),
+                true,
+                &[2, 0, 0, 0, 0, 0, 0, 0, b'a', 0]
+            ),
+            V::Nvarchar("a".to_string())
+        );
+        assert_eq!(single(T::Varbinary(L::Max), true, &[0xFF; 8]), V::Null);
+    }
+
+    #[test]
+    fn single_byte_strings_use_the_collation_code_page() {
+        let varchar = |collation: Option<&str>, bytes: &[u8]| {
+            let mut header = header(T::Varchar(L::Bounded(10)), false);
+            header.collation = collation.map(str::to_string);
+            let mut bytes = bytes.to_vec();
+            bytes.splice(0..0, [bytes.len() as u8, 0]);
+            rows(&[header], &bytes).map(|mut r| r.remove(0).remove(0))
+        };
+
+        let euro = V::Varchar("\u{20AC}".to_string());
+        assert_eq!(varchar(None, &[0x80]).unwrap(), euro);
+        assert_eq!(
+            varchar(Some("Latin1_General_CI_AS"), &[0x80]).unwrap(),
+            euro
+        );
+        assert_eq!(
+            varchar(
+                Some("Latin1_General_100_CI_AS_SC_UTF8"),
+                &[0xE2, 0x82, 0xAC]
+            )
+            .unwrap(),
+            euro
+        );
+        assert_eq!(
+            varchar(Some("SQL_Latin1_General_CP1_CI_AS"), &[0xC3, 0xA9]).unwrap(),
+            V::Varchar("\u{C3}\u{A9}".to_string())
+        );
+        assert_eq!(
+            varchar(Some("Cyrillic_General_CI_AS"), b"abc").unwrap(),
+            V::Varchar("abc".to_string())
+        );
+        let err = varchar(Some("Cyrillic_General_CI_AS"), &[0xC0]).unwrap_err();
+        assert!(err.to_string().starts_with("column c"));
+    }
+
+    #[test]
+    fn null_in_non_nullable_column_is_an_error() {
+        let err = rows(&[header(T::Varchar(L::Bounded(1)), false)], &[0xFF, 0xFF]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("NULL value in non-nullable column"));
+    }
+
+    #[test]
+    fn corrupt_prefix_is_an_error() {
+        let mut bytes = vec![0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0];
+        bytes.extend_from_slice(b"abc");
+        let err = rows(&[header(T::Varbinary(L::Max), true)], &bytes).unwrap_err();
+        assert_eq!(err.to_string(), "Truncated BCP row");
+
+        let err = rows(&[header(T::Varchar(L::Bounded(2)), true)], &[3, 0, 1, 2, 3]).unwrap_err();
+        assert!(err.to_string().contains("holds at most 2"));
+    }
+
+    #[test]
+    fn end_of_stream() {
+        let headers = [header(T::Int, false), header(T::Int, true)];
+        assert_eq!(rows(&headers, &[]).unwrap(), Vec::<Vec<V>>::new());
+        assert!(rows(&headers, &[1, 0, 0, 0]).is_err());
+        assert!(rows(&[], &[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn decimal_and_money_signs() {
+        let decimal = single(T::Decimal(5, 2), false, &[7, 5, 2, 0, 57, 48, 0, 0]);
+        assert_eq!(decimal, V::Decimal(Decimal::new(-12345, 2)));
+        let numeric = single(T::Numeric(5, 2), false, &[7, 5, 2, 1, 57, 48, 0, 0]);
+        assert_eq!(numeric, V::Numeric(Decimal::new(12345, 2)));
+
+        let money = single(
+            T::Money,
+            false,
+            &[0xFF, 0xFF, 0xFF, 0xFF, 199, 207, 0xFF, 0xFF],
+        );
+        assert_eq!(money, V::Money(Decimal::new(-12345, 4)));
+        let smallmoney = single(T::SmallMoney, false, &[199, 207, 0xFF, 0xFF]);
+        assert_eq!(smallmoney, V::SmallMoney(Decimal::new(-12345, 4)));
+    }
+
+    fn text(value: V) -> String {
+        match value {
+            V::Date(d) => d.to_string(),
+            V::Time(t) => t.to_string(),
+            V::DateTime(dt) | V::SmallDateTime(dt) | V::DateTime2(dt) => dt.to_string(),
+            V::DateTimeOffset(dto) => dto.to_string(),
+            other => panic!("Not a temporal value: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn date_and_time() {
+        assert_eq!(text(single(T::Date, false, &[3, 70, 70, 11])), "2024-01-02");
+        assert_eq!(
+            text(single(T::Time(7), false, &[5, 7, 23, 104, 183, 25])),
+            "03:04:05.1234567"
+        );
+        assert_eq!(
+            text(single(
+                T::DateTime,
+                false,
+                &[235, 176, 0, 0, 242, 143, 50, 0]
+            )),
+            "2024-01-02 03:04:05.500"
+        );
+        assert_eq!(
+            text(single(
+                T::DateTime2(7),
+                false,
+                &[8, 7, 23, 104, 183, 25, 70, 70, 11]
+            )),
+            "2024-01-02 03:04:05.1234567"
+        );
+        assert_eq!(
+            text(single(
+                T::DateTimeOffset(0),
+                false,
+                &[8, 5, 15, 0, 70, 70, 11, 120, 0]
+            )),
+            "2024-01-02 03:04:05 +02:00"
+        );
+    }
+
+    /// Builds the headers of a `[dbo].[T]` table from the name, type, column
+    /// properties and type properties of its columns
+    fn model_headers(columns: &[(&str, &str, &str, &str)]) -> Vec<TableColumnHeader> {
+        let columns: String = columns
+            .iter()
+            .map(|(name, ty, properties, facets)| {
+                format!(
+                    r#"<Entry><Element Type="SqlSimpleColumn" Name="[dbo].[T].[{name}]">{properties}
+                    <Relationship Name="TypeSpecifier"><Entry><Element Type="SqlTypeSpecifier">{facets}
+                      <Relationship Name="Type"><Entry><References ExternalSource="BuiltIns" Name="[{ty}]" /></Entry></Relationship>
+                    </Element></Entry></Relationship>
+                    </Element></Entry>"#
+                )
+            })
+            .collect();
+        let xml = format!(
+            r#"<DataSchemaModel><Model>
+            <Element Type="SqlTable" Name="[dbo].[T]">
+              <Relationship Name="Columns">{columns}</Relationship>
+              <Relationship Name="Schema"><Entry><References ExternalSource="BuiltIns" Name="[dbo]" /></Entry></Relationship>
+            </Element>
+            </Model></DataSchemaModel>"#
+        );
+        let model = SimpleDacPacModel::from(&crate::from_xml(&xml).unwrap());
+        TableColumnHeader::for_table(&model.tables[0], None).unwrap()
+    }
+
+    #[test]
+    fn time_scale_follows_the_value_length() {
+        // A model scale that doesn't match the stored length is not trusted
+        assert_eq!(
+            text(single(
+                T::DateTime2(0),
+                false,
+                &[8, 7, 23, 104, 183, 25, 70, 70, 11]
+            )),
+            "2024-01-02 03:04:05.1234567"
+        );
+        assert_eq!(
+            text(single(T::Time(7), false, &[3, 5, 15, 0])),
+            "00:00:38.45"
+        );
+        assert_eq!(
+            text(single(
+                T::DateTimeOffset(3),
+                false,
+                &[10, 7, 23, 104, 183, 25, 70, 70, 11, 0, 0]
+            )),
+            "2024-01-02 03:04:05.1234567 +00:00"
+        );
+    }
+
+    #[test]
+    fn columns_without_is_nullable_are_nullable() {
+        let headers = model_headers(&[
+            (
+                "Id",
+                "int",
+                r#"<Property Name="IsNullable" Value="False" />"#,
+                "",
+            ),
+            ("Value", "int", "", ""),
+        ]);
+        assert!(!headers[0].nullable);
+        assert!(headers[1].nullable);
+
+        let bytes = [1, 0, 0, 0, 0xFF, 2, 0, 0, 0, 4, 3, 0, 0, 0];
+        assert_eq!(
+            rows(&headers, &bytes).unwrap(),
+            vec![vec![V::Int(1), V::Null], vec![V::Int(2), V::Int(3)]]
+        );
+    }
+
+    #[test]
+    fn facets_left_out_of_the_model_have_their_default() {
+        let headers = model_headers(&[
+            ("F", "float", "", ""),
+            ("D", "decimal", "", ""),
+            ("T", "datetime2", "", ""),
+            (
+                "N",
+                "numeric",
+                "",
+                r#"<Property Name="Precision" Value="10" />"#,
+            ),
+        ]);
+        assert_eq!(headers[0].ty, T::Float(53));
+        assert_eq!(headers[1].ty, T::Decimal(18, 0));
+        assert_eq!(headers[2].ty, T::DateTime2(7));
+        assert_eq!(headers[3].ty, T::Numeric(10, 0));
+    }
+}
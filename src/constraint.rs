@@ -0,0 +1,191 @@
+//! Table constraints.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::element::RawElement;
+use crate::name::ObjectName;
+
+#[derive(Debug, PartialEq)]
+pub struct SqlPrimaryKeyConstraint {
+    pub name: Option<ObjectName>,
+    pub defining_table: ObjectName,
+    pub column_specifications: Vec<SqlIndexedColumnSpecification>,
+    pub is_clustered: bool,
+    pub fill_factor: Option<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlForeignKeyConstraint {
+    pub name: Option<ObjectName>,
+    pub defining_table: ObjectName,
+    pub columns: Vec<ObjectName>,
+    pub foreign_table: ObjectName,
+    pub foreign_columns: Vec<ObjectName>,
+    pub delete_action: ForeignKeyAction,
+    pub update_action: ForeignKeyAction,
+    pub is_not_for_replication: bool,
+    pub with_no_check: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlDefaultConstraint {
+    pub name: Option<ObjectName>,
+    pub defining_table: ObjectName,
+    pub for_column: ObjectName,
+    /// The default expression, as written in the source, e.g. `(getdate())`
+    pub expression: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlUniqueConstraint {
+    pub name: Option<ObjectName>,
+    pub defining_table: ObjectName,
+    pub column_specifications: Vec<SqlIndexedColumnSpecification>,
+    pub is_clustered: bool,
+    pub filegroup: Option<ObjectName>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlCheckConstraint {
+    pub name: Option<ObjectName>,
+    pub defining_table: ObjectName,
+    /// The check expression, as written in the source
+    pub expression: String,
+    pub is_not_for_replication: bool,
+    /// `false` for constraints created `WITH NOCHECK`
+    pub is_checked: bool,
+}
+
+/// `ON DELETE`/`ON UPDATE` action of a foreign key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ForeignKeyAction {
+    #[default]
+    NoAction,
+    Cascade,
+    SetNull,
+    SetDefault,
+}
+
+/// A key column of a constraint or an index
+#[derive(Debug, PartialEq)]
+pub struct SqlIndexedColumnSpecification {
+    pub column: ObjectName,
+    pub is_ascending: bool,
+}
+
+impl TryFrom<&RawElement> for SqlPrimaryKeyConstraint {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlPrimaryKeyConstraint {
+            defining_table: e.required_object_reference("DefiningTable")?,
+            column_specifications: column_specifications(e)?,
+            is_clustered: e.bool_property("IsClustered", true),
+            fill_factor: e.parse_property("FillFactor")?,
+            name: constraint_name(e)?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlForeignKeyConstraint {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlForeignKeyConstraint {
+            defining_table: e.required_object_reference("DefiningTable")?,
+            columns: e.object_references("Columns")?,
+            foreign_table: e.required_object_reference("ForeignTable")?,
+            foreign_columns: e.object_references("ForeignColumns")?,
+            delete_action: e.parse_property("DeleteAction")?.unwrap_or_default(),
+            update_action: e.parse_property("UpdateAction")?.unwrap_or_default(),
+            is_not_for_replication: e.bool_property("IsNotForReplication", false),
+            // Fall back on the inverse flag when `WithNoCheck` isn't there
+            with_no_check: e.bool_property("WithNoCheck", !e.bool_property("IsChecked", true)),
+            name: constraint_name(e)?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlDefaultConstraint {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlDefaultConstraint {
+            defining_table: e.required_object_reference("DefiningTable")?,
+            for_column: e.required_object_reference("ForColumn")?,
+            expression: e
+                .property_value("DefaultExpressionScript")
+                .ok_or_else(|| anyhow!("{} has no DefaultExpressionScript", e.display_name()))?,
+            name: constraint_name(e)?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlUniqueConstraint {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlUniqueConstraint {
+            defining_table: e.required_object_reference("DefiningTable")?,
+            column_specifications: column_specifications(e)?,
+            is_clustered: e.bool_property("IsClustered", false),
+            filegroup: e.object_reference("Filegroup")?,
+            name: constraint_name(e)?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlCheckConstraint {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlCheckConstraint {
+            defining_table: e.required_object_reference("DefiningTable")?,
+            expression: e
+                .property_value("CheckExpressionScript")
+                .ok_or_else(|| anyhow!("{} has no CheckExpressionScript", e.display_name()))?,
+            is_not_for_replication: e.bool_property("IsNotForReplication", false),
+            is_checked: e.bool_property("IsChecked", true),
+            name: constraint_name(e)?,
+        })
+    }
+}
+
+impl FromStr for ForeignKeyAction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "0" | "NoAction" => Ok(ForeignKeyAction::NoAction),
+            "1" | "Cascade" => Ok(ForeignKeyAction::Cascade),
+            "2" | "SetNull" => Ok(ForeignKeyAction::SetNull),
+            "3" | "SetDefault" => Ok(ForeignKeyAction::SetDefault),
+            _ => Err(anyhow!("Unknown foreign key action `{s}`")),
+        }
+    }
+}
+
+impl TryFrom<&RawElement> for SqlIndexedColumnSpecification {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlIndexedColumnSpecification {
+            column: e.required_object_reference("Column")?,
+            is_ascending: e.bool_property("IsAscending", true),
+        })
+    }
+}
+
+/// Reads the `ColumnSpecifications` relationship of a constraint or an index
+pub(crate) fn column_specifications(e: &RawElement) -> Result<Vec<SqlIndexedColumnSpecification>> {
+    e.elements("ColumnSpecifications")
+        .into_iter()
+        .map(SqlIndexedColumnSpecification::try_from)
+        .collect()
+}
+
+fn constraint_name(e: &RawElement) -> Result<Option<ObjectName>> {
+    e.name.as_deref().map(str::parse).transpose()
+}
@@ -0,0 +1,180 @@
+//! Database level settings.
+//!
+//! DacFx leaves out the properties that have their default value, so the
+//! defaults used here are the ones of `CREATE DATABASE`.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::element::RawElement;
+use crate::Property;
+
+#[derive(Debug, PartialEq)]
+pub struct SqlDatabaseOptions {
+    pub collation: Option<String>,
+    pub compatibility_level: Option<u16>,
+    pub recovery_mode: Option<RecoveryMode>,
+    pub containment: Containment,
+    pub page_verify_mode: Option<PageVerifyMode>,
+    pub is_read_committed_snapshot_on: bool,
+    pub allow_snapshot_isolation: bool,
+    pub is_memory_optimized_elevated_to_snapshot: bool,
+    pub is_ansi_null_default_on: bool,
+    pub is_ansi_nulls_on: bool,
+    pub is_ansi_padding_on: bool,
+    pub is_ansi_warnings_on: bool,
+    pub is_arith_abort_on: bool,
+    pub is_concat_null_yields_null_on: bool,
+    pub is_numeric_round_abort_on: bool,
+    pub is_quoted_identifier_on: bool,
+    pub is_recursive_triggers_on: bool,
+    pub is_auto_close_on: bool,
+    pub is_auto_shrink_on: bool,
+    pub is_auto_create_statistics_on: bool,
+    pub is_auto_update_statistics_on: bool,
+    pub is_auto_update_statistics_async_on: bool,
+    pub is_trustworthy: bool,
+    pub is_db_chaining_on: bool,
+    pub is_full_text_enabled: bool,
+    pub default_language: Option<String>,
+    pub default_full_text_language: Option<String>,
+    /// All properties, including the ones without a field above
+    pub properties: Vec<Property>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryMode {
+    Full,
+    BulkLogged,
+    Simple,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Containment {
+    #[default]
+    None,
+    Partial,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageVerifyMode {
+    None,
+    TornPageDetection,
+    Checksum,
+}
+
+/// Properties mapped to a field of [`SqlDatabaseOptions`]
+pub(crate) const TYPED_PROPERTIES: &[&str] = &[
+    "Collation",
+    "CompatibilityLevel",
+    "RecoveryMode",
+    "Containment",
+    "PageVerifyMode",
+    "IsReadCommittedSnapshot",
+    "AllowSnapshotIsolation",
+    "IsAllowSnapshotIsolation",
+    "IsMemoryOptimizedElevatedToSnapshot",
+    "IsAnsiNullDefaultOn",
+    "IsAnsiNullsOn",
+    "IsAnsiPaddingOn",
+    "IsAnsiWarningsOn",
+    "IsArithAbortOn",
+    "IsConcatNullYieldsNullOn",
+    "IsNumericRoundAbortOn",
+    "IsQuotedIdentifierOn",
+    "IsRecursiveTriggersOn",
+    "IsAutoCloseOn",
+    "IsAutoShrinkOn",
+    "IsAutoCreateStatisticsOn",
+    "IsAutoUpdateStatisticsOn",
+    "IsAutoUpdateStatisticsAsyncOn",
+    "IsTrustworthy",
+    "IsDbChainingOn",
+    "IsFullTextEnabled",
+    "DefaultLanguage",
+    "DefaultFullTextLanguage",
+];
+
+impl TryFrom<&RawElement> for SqlDatabaseOptions {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlDatabaseOptions {
+            collation: non_empty(e.property_value("Collation")),
+            compatibility_level: e.parse_property("CompatibilityLevel")?,
+            recovery_mode: e.parse_property("RecoveryMode")?,
+            containment: e.parse_property("Containment")?.unwrap_or_default(),
+            page_verify_mode: page_verify_mode(e)?,
+            is_read_committed_snapshot_on: e.bool_property("IsReadCommittedSnapshot", false),
+            allow_snapshot_isolation: e.bool_property(
+                "AllowSnapshotIsolation",
+                e.bool_property("IsAllowSnapshotIsolation", false),
+            ),
+            is_memory_optimized_elevated_to_snapshot: e
+                .bool_property("IsMemoryOptimizedElevatedToSnapshot", false),
+            is_ansi_null_default_on: e.bool_property("IsAnsiNullDefaultOn", false),
+            is_ansi_nulls_on: e.bool_property("IsAnsiNullsOn", false),
+            is_ansi_padding_on: e.bool_property("IsAnsiPaddingOn", false),
+            is_ansi_warnings_on: e.bool_property("IsAnsiWarningsOn", false),
+            is_arith_abort_on: e.bool_property("IsArithAbortOn", false),
+            is_concat_null_yields_null_on: e.bool_property("IsConcatNullYieldsNullOn", false),
+            is_numeric_round_abort_on: e.bool_property("IsNumericRoundAbortOn", false),
+            is_quoted_identifier_on: e.bool_property("IsQuotedIdentifierOn", false),
+            is_recursive_triggers_on: e.bool_property("IsRecursiveTriggersOn", false),
+            is_auto_close_on: e.bool_property("IsAutoCloseOn", false),
+            is_auto_shrink_on: e.bool_property("IsAutoShrinkOn", false),
+            is_auto_create_statistics_on: e.bool_property("IsAutoCreateStatisticsOn", true),
+            is_auto_update_statistics_on: e.bool_property("IsAutoUpdateStatisticsOn", true),
+            is_auto_update_statistics_async_on: e
+                .bool_property("IsAutoUpdateStatisticsAsyncOn", false),
+            is_trustworthy: e.bool_property("IsTrustworthy", false),
+            is_db_chaining_on: e.bool_property("IsDbChainingOn", false),
+            is_full_text_enabled: e.bool_property("IsFullTextEnabled", false),
+            default_language: non_empty(e.property_value("DefaultLanguage")),
+            default_full_text_language: non_empty(e.property_value("DefaultFullTextLanguage")),
+            properties: e.properties.clone(),
+        })
+    }
+}
+
+impl FromStr for RecoveryMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "0" | "Full" => Ok(RecoveryMode::Full),
+            "1" | "BulkLogged" => Ok(RecoveryMode::BulkLogged),
+            "2" | "Simple" => Ok(RecoveryMode::Simple),
+            _ => Err(anyhow!("Unknown recovery mode `{s}`")),
+        }
+    }
+}
+
+impl FromStr for Containment {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "0" | "None" => Ok(Containment::None),
+            "1" | "Partial" => Ok(Containment::Partial),
+            _ => Err(anyhow!("Unknown containment `{s}`")),
+        }
+    }
+}
+
+/// `None` when the mode isn't specified
+fn page_verify_mode(e: &RawElement) -> Result<Option<PageVerifyMode>> {
+    match e.property_value("PageVerifyMode").as_deref() {
+        None | Some("0") | Some("NotSpecified") => Ok(None),
+        Some("1") | Some("None") => Ok(Some(PageVerifyMode::None)),
+        Some("2") | Some("TornPageDetection") => Ok(Some(PageVerifyMode::TornPageDetection)),
+        Some("3") | Some("Checksum") => Ok(Some(PageVerifyMode::Checksum)),
+        Some(s) => Err(anyhow!("Unknown page verify mode `{s}`")),
+    }
+}
+
+/// DacFx writes empty values for unset names
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.is_empty())
+}
@@ -0,0 +1,52 @@
+//! Exact values of `decimal`, `numeric`, `money` and `smallmoney` columns.
+//!
+//! A conversion to `rust_decimal::Decimal` is available behind the
+//! `rust_decimal` cargo feature.
+
+use std::fmt;
+
+/// The exact number `mantissa * 10^-scale`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Decimal {
+    pub mantissa: i128,
+    pub scale: u8,
+}
+
+impl Decimal {
+    pub fn new(mantissa: i128, scale: u8) -> Decimal {
+        Decimal { mantissa, scale }
+    }
+}
+
+/// Formats with exactly `scale` fractional digits, as SQL Server does
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let scale = usize::from(self.scale);
+        let digits = format!("{digits:0>width$}", width = scale + 1);
+        let (int, frac) = digits.split_at(digits.len() - scale);
+
+        if self.mantissa < 0 {
+            f.write_str("-")?;
+        }
+        f.write_str(int)?;
+        if !frac.is_empty() {
+            write!(f, ".{frac}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl TryFrom<Decimal> for rust_decimal::Decimal {
+    type Error = anyhow::Error;
+
+    /// Fails when the value needs more than the 96 bit mantissa or the scale
+    /// of 28 that `rust_decimal` supports
+    fn try_from(d: Decimal) -> anyhow::Result<Self> {
+        Ok(rust_decimal::Decimal::try_from_i128_with_scale(
+            d.mantissa,
+            u32::from(d.scale),
+        )?)
+    }
+}
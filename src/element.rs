@@ -0,0 +1,484 @@
+//! Generic building blocks of model.xml elements.
+//!
+//! Every element is deserialized into a [`RawElement`] first, then converted
+//! into its typed struct through `TryFrom<&RawElement>`. This avoids writing a
+//! visitor for every element whose relationships are only told apart by their
+//! `Name` attribute, and keeps the element when the conversion fails.
+//!
+//! DacFx writes enumerated properties as their numeric value, so the
+//! `FromStr` impls of the typed enums accept the number as well as the name.
+//! Elements declared without a name, such as unnamed constraints, have no
+//! `Name` attribute.
+//!
+//! [`ElementGraph`] holds the elements, annotations and header of a model.xml
+//! in this form, for tooling that needs elements the typed structs don't
+//! cover. Attributes and children DacFx doesn't write are not kept.
+
+use std::collections::HashMap;
+use std::fs::File;
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::name::ObjectName;
+use crate::Property;
+
+/// An `Element` with its properties, relationships and annotations
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct RawElement {
+    #[serde(rename = "@Type", default)]
+    pub ty: String,
+    #[serde(rename = "@Name")]
+    pub name: Option<String>,
+    /// Tells apart unnamed elements that annotations are attached to
+    #[serde(rename = "@Disambiguator")]
+    pub disambiguator: Option<String>,
+    #[serde(rename = "Property", default)]
+    pub properties: Vec<Property>,
+    #[serde(rename = "Relationship", default)]
+    pub relationships: Vec<Relationship>,
+    #[serde(rename = "Annotation", default)]
+    pub annotations: Vec<Annotation>,
+    #[serde(rename = "AttachedAnnotation", default)]
+    pub attached_annotations: Vec<AttachedAnnotation>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Relationship {
+    #[serde(rename = "@Name")]
+    pub name: String,
+    #[serde(rename = "Entry", default)]
+    pub entries: Vec<RelationshipEntry>,
+}
+
+/// An `Entry` holds either a reference to another element, or an inline element
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct RelationshipEntry {
+    #[serde(rename = "References")]
+    pub references: Option<Reference>,
+    #[serde(rename = "Element")]
+    pub element: Option<RawElement>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Reference {
+    #[serde(rename = "@Name")]
+    pub name: String,
+    /// `BuiltIns` for system objects, which aren't elements of the model
+    #[serde(rename = "@ExternalSource")]
+    pub external_source: Option<String>,
+    #[serde(rename = "@Disambiguator")]
+    pub disambiguator: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Annotation {
+    #[serde(rename = "@Type")]
+    pub ty: String,
+    #[serde(rename = "@Name")]
+    pub name: Option<String>,
+    #[serde(rename = "@Disambiguator")]
+    pub disambiguator: Option<String>,
+    #[serde(rename = "Property", default)]
+    pub properties: Vec<Property>,
+}
+
+/// Links an element to an `Annotation` defined elsewhere with the same
+/// disambiguator
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct AttachedAnnotation {
+    #[serde(rename = "@Disambiguator")]
+    pub disambiguator: String,
+}
+
+impl RawElement {
+    /// Returns the element name, or a placeholder for error messages
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or("<unnamed>")
+    }
+
+    pub fn property(&self, name: &str) -> Option<&Property> {
+        self.properties.iter().find(|p| p.name == name)
+    }
+
+    /// Returns the value of a property, `None` when absent
+    pub fn property_value(&self, name: &str) -> Option<String> {
+        self.property(name).map(|p| p.get_value())
+    }
+
+    /// Returns a `True`/`False` property, `default` when absent
+    pub fn bool_property(&self, name: &str, default: bool) -> bool {
+        match self.property_value(name) {
+            Some(v) => v == "True",
+            None => default,
+        }
+    }
+
+    /// Parses a property, `None` when absent
+    pub fn parse_property<T>(&self, name: &str) -> Result<Option<T>>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.property_value(name)
+            .map(|v| {
+                v.parse()
+                    .map_err(|e| anyhow!("Invalid {name} `{v}` on {}: {e}", self.display_name()))
+            })
+            .transpose()
+    }
+
+    pub fn relationship(&self, name: &str) -> Option<&Relationship> {
+        self.relationships.iter().find(|r| r.name == name)
+    }
+
+    /// Returns the names of the elements referenced by a relationship
+    pub fn references(&self, name: &str) -> Vec<String> {
+        self.relationship(name)
+            .map(|r| {
+                r.entries
+                    .iter()
+                    .filter_map(|e| e.references.as_ref())
+                    .map(|r| r.name.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the name of the single element referenced by a relationship
+    pub fn reference(&self, name: &str) -> Option<String> {
+        self.references(name).into_iter().next()
+    }
+
+    /// Like [`RawElement::reference`], but fails when the relationship is missing
+    pub fn required_reference(&self, name: &str) -> Result<String> {
+        self.reference(name)
+            .ok_or_else(|| anyhow!("{} has no {name} relationship", self.display_name()))
+    }
+
+    /// Parses the element name, `kind` naming the element when it has none
+    pub fn object_name(&self, kind: &str) -> Result<ObjectName> {
+        self.name
+            .as_deref()
+            .ok_or_else(|| anyhow!("{kind} has no Name attribute"))?
+            .parse()
+    }
+
+    /// Like [`RawElement::references`], parsing the names
+    pub fn object_references(&self, name: &str) -> Result<Vec<ObjectName>> {
+        self.references(name).iter().map(|r| r.parse()).collect()
+    }
+
+    /// Like [`RawElement::reference`], parsing the name
+    pub fn object_reference(&self, name: &str) -> Result<Option<ObjectName>> {
+        self.reference(name).map(|r| r.parse()).transpose()
+    }
+
+    /// Like [`RawElement::required_reference`], parsing the name
+    pub fn required_object_reference(&self, name: &str) -> Result<ObjectName> {
+        self.required_reference(name)?.parse()
+    }
+
+    /// Returns the name of the schema the element belongs to
+    pub fn schema(&self) -> Result<String> {
+        Ok(self.required_object_reference("Schema")?.name)
+    }
+
+    /// Returns the inline elements of a relationship
+    pub fn elements(&self, name: &str) -> Vec<&RawElement> {
+        self.relationship(name)
+            .map(|r| {
+                r.entries
+                    .iter()
+                    .filter_map(|e| e.element.as_ref())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Every element of a model.xml, with lookups by name and by type.
+///
+/// Inline elements, e.g. table columns, are indexed along with the top level
+/// ones. Names aren't unique across types: a schema and a user can share one.
+#[derive(Debug)]
+pub struct ElementGraph {
+    elements: Vec<RawElement>,
+    header: ModelHeader,
+    /// Annotations written at the model level, outside of any element
+    annotations: Vec<Annotation>,
+    /// Every element in document order, parents before their inline elements
+    paths: Vec<ElementPath>,
+    by_name: HashMap<String, Vec<usize>>,
+    by_type: HashMap<String, Vec<usize>>,
+}
+
+/// Where an element sits: a top level element, then the relationship and
+/// entry indexes leading to an inline element
+#[derive(Debug, Clone)]
+struct ElementPath {
+    top: usize,
+    steps: Vec<(usize, usize)>,
+}
+
+#[derive(Deserialize)]
+struct RawDataSchemaModel {
+    #[serde(rename = "@FileFormatVersion")]
+    file_format_version: Option<String>,
+    #[serde(rename = "@SchemaVersion")]
+    schema_version: Option<String>,
+    #[serde(rename = "@DspName")]
+    dsp_name: Option<String>,
+    #[serde(rename = "@CollationLcid")]
+    collation_lcid: Option<String>,
+    #[serde(rename = "@CollationCaseSensitive")]
+    collation_case_sensitive: Option<String>,
+    #[serde(rename = "Header")]
+    header: Option<RawHeader>,
+    #[serde(rename = "Model")]
+    model: RawModel,
+}
+
+#[derive(Deserialize)]
+struct RawHeader {
+    #[serde(rename = "CustomData", default)]
+    custom_data: Vec<CustomData>,
+}
+
+/// The attributes of the root `DataSchemaModel` element and its `Header`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelHeader {
+    pub file_format_version: Option<String>,
+    pub schema_version: Option<String>,
+    /// The target platform, e.g. `...Sql160DatabaseSchemaProvider`
+    pub dsp_name: Option<String>,
+    pub collation_lcid: Option<String>,
+    pub collation_case_sensitive: Option<String>,
+    pub custom_data: Vec<CustomData>,
+}
+
+/// A `CustomData` entry of the header, e.g. the `AnsiNulls` setting or a
+/// referenced dacpac
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CustomData {
+    #[serde(rename = "@Category")]
+    pub category: String,
+    #[serde(rename = "@Type")]
+    pub ty: Option<String>,
+    #[serde(rename = "Metadata", default)]
+    pub metadata: Vec<Metadata>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Metadata {
+    #[serde(rename = "@Name")]
+    pub name: String,
+    #[serde(rename = "@Value")]
+    pub value: String,
+}
+
+#[derive(Deserialize)]
+struct RawModel {
+    #[serde(rename = "Element", default)]
+    elements: Vec<RawElement>,
+    #[serde(rename = "Annotation", default)]
+    annotations: Vec<Annotation>,
+}
+
+impl ElementGraph {
+    pub fn from_xml(xml: &str) -> Result<ElementGraph> {
+        let dsm: RawDataSchemaModel = quick_xml::de::from_str(xml)?;
+        let mut graph = ElementGraph::new(dsm.model.elements);
+        graph.annotations = dsm.model.annotations;
+        graph.header = ModelHeader {
+            file_format_version: dsm.file_format_version,
+            schema_version: dsm.schema_version,
+            dsp_name: dsm.dsp_name,
+            collation_lcid: dsm.collation_lcid,
+            collation_case_sensitive: dsm.collation_case_sensitive,
+            custom_data: dsm.header.map(|h| h.custom_data).unwrap_or_default(),
+        };
+        Ok(graph)
+    }
+
+    /// Reads the model.xml of a dacpac or bacpac
+    pub fn from_file(file: &File) -> Result<ElementGraph> {
+        ElementGraph::from_xml(&crate::read_model_xml(file)?)
+    }
+
+    pub fn new(elements: Vec<RawElement>) -> ElementGraph {
+        let mut paths = Vec::new();
+        let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut by_type: HashMap<String, Vec<usize>> = HashMap::new();
+
+        let mut pending: Vec<ElementPath> = (0..elements.len())
+            .rev()
+            .map(|top| ElementPath {
+                top,
+                steps: Vec::new(),
+            })
+            .collect();
+        while let Some(path) = pending.pop() {
+            let element = resolve_path(&elements, &path);
+            let i = paths.len();
+            if let Some(name) = &element.name {
+                by_name.entry(name.clone()).or_default().push(i);
+            }
+            by_type.entry(element.ty.clone()).or_default().push(i);
+
+            let mut children = Vec::new();
+            for (r, relationship) in element.relationships.iter().enumerate() {
+                for (e, entry) in relationship.entries.iter().enumerate() {
+                    if entry.element.is_some() {
+                        let mut steps = path.steps.clone();
+                        steps.push((r, e));
+                        children.push(ElementPath {
+                            top: path.top,
+                            steps,
+                        });
+                    }
+                }
+            }
+            pending.extend(children.into_iter().rev());
+            paths.push(path);
+        }
+
+        ElementGraph {
+            elements,
+            header: ModelHeader::default(),
+            annotations: Vec::new(),
+            paths,
+            by_name,
+            by_type,
+        }
+    }
+
+    /// The top level elements, in document order
+    pub fn elements(&self) -> &[RawElement] {
+        &self.elements
+    }
+
+    pub fn header(&self) -> &ModelHeader {
+        &self.header
+    }
+
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    /// Returns the annotation an `AttachedAnnotation` points to
+    pub fn attached_annotation(&self, attached: &AttachedAnnotation) -> Option<&Annotation> {
+        self.annotations
+            .iter()
+            .chain(self.iter().flat_map(|e| e.annotations.iter()))
+            .find(|a| a.disambiguator.as_deref() == Some(attached.disambiguator.as_str()))
+    }
+
+    /// Every element, inline ones included, in document order
+    pub fn iter(&self) -> impl Iterator<Item = &RawElement> {
+        self.paths.iter().map(|p| self.resolve_path(p))
+    }
+
+    /// Returns the elements named `name`, e.g. `[dbo].[Table]`
+    pub fn by_name(&self, name: &str) -> Vec<&RawElement> {
+        self.lookup(&self.by_name, name)
+    }
+
+    /// Returns the elements of type `ty`, e.g. `SqlTable`
+    pub fn by_type(&self, ty: &str) -> Vec<&RawElement> {
+        self.lookup(&self.by_type, ty)
+    }
+
+    /// Returns the element of type `ty` named `name`
+    pub fn get(&self, ty: &str, name: &str) -> Option<&RawElement> {
+        self.by_name(name).into_iter().find(|e| e.ty == ty)
+    }
+
+    /// Returns the element a reference points to. `None` for external
+    /// references, e.g. to built-in types, and for names not in the model.
+    /// When several elements share the name, the first one is returned.
+    pub fn resolve(&self, reference: &Reference) -> Option<&RawElement> {
+        if reference.external_source.is_some() {
+            return None;
+        }
+        self.by_name(&reference.name).into_iter().next()
+    }
+
+    /// Resolves the references of a relationship of `element`, skipping the
+    /// ones that can't be resolved
+    pub fn related(&self, element: &RawElement, relationship: &str) -> Vec<&RawElement> {
+        element
+            .relationship(relationship)
+            .map(|r| {
+                r.entries
+                    .iter()
+                    .filter_map(|e| e.references.as_ref())
+                    .filter_map(|r| self.resolve(r))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn lookup(&self, index: &HashMap<String, Vec<usize>>, key: &str) -> Vec<&RawElement> {
+        index
+            .get(key)
+            .map(|ids| {
+                ids.iter()
+                    .map(|&i| self.resolve_path(&self.paths[i]))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn resolve_path(&self, path: &ElementPath) -> &RawElement {
+        resolve_path(&self.elements, path)
+    }
+}
+
+fn resolve_path<'a>(elements: &'a [RawElement], path: &ElementPath) -> &'a RawElement {
+    let mut element = &elements[path.top];
+    for &(r, e) in &path.steps {
+        element = element.relationships[r].entries[e]
+            .element
+            .as_ref()
+            .expect("paths only lead to inline elements");
+    }
+    element
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_keeps_header_and_script_settings() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<DataSchemaModel FileFormatVersion="1.2" SchemaVersion="2.9" DspName="Microsoft.Data.Tools.Schema.Sql.Sql160DatabaseSchemaProvider" CollationLcid="1033" CollationCaseSensitive="False" xmlns="http://schemas.microsoft.com/sqlserver/dac/Serialization/2012/02">
+  <Header>
+    <CustomData Category="AnsiNulls"><Metadata Name="AnsiNulls" Value="True" /></CustomData>
+  </Header>
+  <Model>
+    <Element Type="SqlProcedure" Name="[dbo].[P]">
+      <Property Name="BodyScript"><Value QuotedIdentifiers="False" AnsiNulls="False"><![CDATA[SELECT 1]]></Value></Property>
+    </Element>
+  </Model>
+</DataSchemaModel>"#;
+        let graph = ElementGraph::from_xml(xml).unwrap();
+
+        let header = graph.header();
+        assert_eq!(header.schema_version.as_deref(), Some("2.9"));
+        assert_eq!(header.collation_lcid.as_deref(), Some("1033"));
+        assert_eq!(header.custom_data[0].category, "AnsiNulls");
+        assert_eq!(header.custom_data[0].metadata[0].value, "True");
+
+        let procedure = graph.by_name("[dbo].[P]")[0];
+        let body = procedure.properties[0].cdata.as_ref().unwrap();
+        assert_eq!(body.text, "SELECT 1");
+        assert_eq!(body.quoted_identifiers.as_deref(), Some("False"));
+        assert_eq!(body.ansi_nulls.as_deref(), Some("False"));
+        assert_eq!(
+            procedure.property_value("BodyScript").as_deref(),
+            Some("SELECT 1")
+        );
+    }
+}
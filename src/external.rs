@@ -0,0 +1,213 @@
+//! PolyBase external data sources, file formats and tables.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::element::RawElement;
+use crate::name::ObjectName;
+use crate::table::SqlTableColumnElement;
+
+#[derive(Debug, PartialEq)]
+pub struct SqlExternalDataSource {
+    pub name: ObjectName,
+    pub data_source_type: ExternalDataSourceType,
+    pub location: String,
+    /// The database scoped credential used to connect
+    pub credential: Option<ObjectName>,
+    /// `RESOURCE_MANAGER_LOCATION` of Hadoop sources
+    pub resource_manager_location: Option<String>,
+    /// `DATABASE_NAME` of RDBMS and shard map manager sources
+    pub database_name: Option<String>,
+    pub shard_map_name: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlExternalFileFormat {
+    pub name: ObjectName,
+    pub format_type: ExternalFileFormatType,
+    /// The following options only apply to delimited text
+    pub field_terminator: Option<String>,
+    pub string_delimiter: Option<String>,
+    pub date_format: Option<String>,
+    pub use_type_default: bool,
+    pub first_row: Option<u32>,
+    pub encoding: Option<ExternalFileEncoding>,
+    /// The codec class given in `DATA_COMPRESSION`
+    pub data_compression: Option<String>,
+    /// The `SERDE_METHOD` of RC files
+    pub serde_method: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlExternalTable {
+    pub name: ObjectName,
+    pub schema: String,
+    pub columns: Vec<SqlTableColumnElement>,
+    pub data_source: ObjectName,
+    /// `None` for RDBMS and shard map manager sources
+    pub file_format: Option<ObjectName>,
+    /// The folder or file of the data, or the remote object
+    pub location: Option<String>,
+    pub reject_type: RejectType,
+    /// The number or the percentage of rows that can be rejected
+    pub reject_value: Option<f64>,
+    /// For percentage reject types, rows read before the percentage is computed
+    pub reject_sample_value: Option<f64>,
+    pub rejected_row_location: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalDataSourceType {
+    Hadoop,
+    Rdbms,
+    ShardMapManager,
+    BlobStorage,
+    /// Sources of SQL Server 2019 and later, whose type is given by the
+    /// location prefix, e.g. `sqlserver://` or `oracle://`
+    ExternalGenerics,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalFileFormatType {
+    DelimitedText,
+    RcFile,
+    Orc,
+    Parquet,
+    Json,
+    Delta,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalFileEncoding {
+    Utf8,
+    Utf16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RejectType {
+    #[default]
+    Value,
+    Percentage,
+}
+
+impl TryFrom<&RawElement> for SqlExternalDataSource {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlExternalDataSource {
+            data_source_type: e
+                .parse_property("DataSourceType")?
+                .unwrap_or(ExternalDataSourceType::Hadoop),
+            location: e
+                .property_value("Location")
+                .ok_or_else(|| anyhow!("{} has no Location", e.display_name()))?,
+            credential: e.object_reference("Credential")?,
+            resource_manager_location: e.property_value("ResourceManagerLocation"),
+            database_name: e.property_value("DatabaseName"),
+            shard_map_name: e.property_value("ShardMapName"),
+            name: e.object_name("External data source")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlExternalFileFormat {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlExternalFileFormat {
+            format_type: e
+                .parse_property("FormatType")?
+                .unwrap_or(ExternalFileFormatType::DelimitedText),
+            field_terminator: e.property_value("FieldTerminator"),
+            string_delimiter: e.property_value("StringDelimiter"),
+            date_format: e.property_value("DateFormat"),
+            use_type_default: e.bool_property("UseTypeDefault", false),
+            first_row: e.parse_property("FirstRow")?,
+            encoding: e.parse_property("Encoding")?,
+            data_compression: e.property_value("DataCompression"),
+            serde_method: e.property_value("SerDeMethod"),
+            name: e.object_name("External file format")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlExternalTable {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        let columns = e
+            .elements("Columns")
+            .into_iter()
+            .map(SqlTableColumnElement::try_from)
+            .collect::<Result<_>>()?;
+
+        Ok(SqlExternalTable {
+            schema: e.schema()?,
+            columns,
+            data_source: e.required_object_reference("DataSource")?,
+            file_format: e.object_reference("FileFormat")?,
+            location: e.property_value("Location"),
+            reject_type: e.parse_property("RejectType")?.unwrap_or_default(),
+            reject_value: e.parse_property("RejectValue")?,
+            reject_sample_value: e.parse_property("RejectSampleValue")?,
+            rejected_row_location: e.property_value("RejectedRowLocation"),
+            name: e.object_name("External table")?,
+        })
+    }
+}
+
+impl FromStr for ExternalDataSourceType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "0" | "Hadoop" => Ok(ExternalDataSourceType::Hadoop),
+            "1" | "Rdbms" => Ok(ExternalDataSourceType::Rdbms),
+            "2" | "ShardMapManager" => Ok(ExternalDataSourceType::ShardMapManager),
+            "3" | "BlobStorage" => Ok(ExternalDataSourceType::BlobStorage),
+            "4" | "ExternalGenerics" => Ok(ExternalDataSourceType::ExternalGenerics),
+            _ => Err(anyhow!("Unknown external data source type `{s}`")),
+        }
+    }
+}
+
+impl FromStr for ExternalFileFormatType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "0" | "DelimitedText" => Ok(ExternalFileFormatType::DelimitedText),
+            "1" | "RcFile" => Ok(ExternalFileFormatType::RcFile),
+            "2" | "Orc" => Ok(ExternalFileFormatType::Orc),
+            "3" | "Parquet" => Ok(ExternalFileFormatType::Parquet),
+            "4" | "Json" => Ok(ExternalFileFormatType::Json),
+            "5" | "Delta" => Ok(ExternalFileFormatType::Delta),
+            _ => Err(anyhow!("Unknown external file format type `{s}`")),
+        }
+    }
+}
+
+impl FromStr for ExternalFileEncoding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "0" | "UTF8" | "Utf8" => Ok(ExternalFileEncoding::Utf8),
+            "1" | "UTF16" | "Utf16" => Ok(ExternalFileEncoding::Utf16),
+            _ => Err(anyhow!("Unknown external file encoding `{s}`")),
+        }
+    }
+}
+
+impl FromStr for RejectType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "0" | "Value" => Ok(RejectType::Value),
+            "1" | "Percentage" => Ok(RejectType::Percentage),
+            _ => Err(anyhow!("Unknown reject type `{s}`")),
+        }
+    }
+}
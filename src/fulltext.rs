@@ -0,0 +1,137 @@
+//! Fulltext catalogs, indexes and stop lists.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::element::RawElement;
+use crate::name::ObjectName;
+
+#[derive(Debug, PartialEq)]
+pub struct SqlFullTextCatalog {
+    pub name: ObjectName,
+    pub is_accent_sensitive: bool,
+    pub is_default: bool,
+    pub authorizer: Option<ObjectName>,
+}
+
+/// A `CREATE FULLTEXT INDEX`. DacFx names these after the table or view they
+/// are defined on, as there is at most one per object.
+#[derive(Debug, PartialEq)]
+pub struct SqlFullTextIndex {
+    pub name: ObjectName,
+    pub indexed_object: ObjectName,
+    pub columns: Vec<SqlFullTextIndexColumnSpecifier>,
+    /// The unique index given in `KEY INDEX`
+    pub key_index: ObjectName,
+    /// `None` when the index is in the default catalog
+    pub catalog: Option<ObjectName>,
+    pub filegroup: Option<ObjectName>,
+    /// `None` with the system stop list or `STOPLIST = OFF`
+    pub stop_list: Option<ObjectName>,
+    pub is_stop_list_off: bool,
+    pub change_tracking: ChangeTracking,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlFullTextStopList {
+    pub name: ObjectName,
+    pub authorizer: Option<ObjectName>,
+}
+
+/// A column of a fulltext index
+#[derive(Debug, PartialEq)]
+pub struct SqlFullTextIndexColumnSpecifier {
+    pub column: ObjectName,
+    /// The LCID given in `LANGUAGE`, `None` for the default fulltext language
+    pub language_id: Option<u32>,
+    /// For `varbinary` columns, the column holding the document type
+    pub type_column: Option<ObjectName>,
+    pub is_statistical_semantics: bool,
+}
+
+/// The `CHANGE_TRACKING` option of a fulltext index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChangeTracking {
+    #[default]
+    Auto,
+    Manual,
+    Off,
+    /// `OFF, NO POPULATION`
+    OffNoPopulation,
+}
+
+impl TryFrom<&RawElement> for SqlFullTextCatalog {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlFullTextCatalog {
+            is_accent_sensitive: e.bool_property("IsAccentSensitive", true),
+            is_default: e.bool_property("IsDefault", false),
+            authorizer: e.object_reference("Authorizer")?,
+            name: e.object_name("Fulltext catalog")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlFullTextIndex {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        let columns = e
+            .elements("Columns")
+            .into_iter()
+            .map(SqlFullTextIndexColumnSpecifier::try_from)
+            .collect::<Result<_>>()?;
+
+        Ok(SqlFullTextIndex {
+            indexed_object: e.required_object_reference("IndexedObject")?,
+            columns,
+            key_index: e.required_object_reference("KeyName")?,
+            catalog: e.object_reference("Catalog")?,
+            filegroup: e.object_reference("Filegroup")?,
+            stop_list: e.object_reference("StopList")?,
+            is_stop_list_off: e.bool_property("DisableStopList", false),
+            change_tracking: e.parse_property("ChangeTracking")?.unwrap_or_default(),
+            name: e.object_name("Fulltext index")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlFullTextStopList {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlFullTextStopList {
+            authorizer: e.object_reference("Authorizer")?,
+            name: e.object_name("Fulltext stop list")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlFullTextIndexColumnSpecifier {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlFullTextIndexColumnSpecifier {
+            column: e.required_object_reference("Column")?,
+            language_id: e.parse_property("LanguageId")?,
+            type_column: e.object_reference("TypeColumn")?,
+            is_statistical_semantics: e.bool_property("IsStatisticalSemantics", false),
+        })
+    }
+}
+
+impl FromStr for ChangeTracking {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "0" | "Auto" => Ok(ChangeTracking::Auto),
+            "1" | "Manual" => Ok(ChangeTracking::Manual),
+            "2" | "Off" => Ok(ChangeTracking::Off),
+            "3" | "OffNoPopulation" => Ok(ChangeTracking::OffNoPopulation),
+            _ => Err(anyhow!("Unknown change tracking option `{s}`")),
+        }
+    }
+}
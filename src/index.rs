@@ -0,0 +1,204 @@
+//! Indexes.
+//!
+//! Every index references the table or view it is defined on through its
+//! `IndexedObject` relationship.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::constraint::{column_specifications, SqlIndexedColumnSpecification};
+use crate::element::RawElement;
+use crate::name::ObjectName;
+
+/// A rowstore index, i.e. `CREATE [UNIQUE] [CLUSTERED] INDEX`
+#[derive(Debug, PartialEq)]
+pub struct SqlIndex {
+    pub name: ObjectName,
+    pub indexed_object: ObjectName,
+    pub column_specifications: Vec<SqlIndexedColumnSpecification>,
+    /// Non-key columns of the `INCLUDE` clause
+    pub included_columns: Vec<ObjectName>,
+    /// The `WHERE` clause of a filtered index, as written in the source
+    pub filter_predicate: Option<String>,
+    pub is_unique: bool,
+    pub is_clustered: bool,
+    pub fill_factor: Option<u8>,
+    pub is_disabled: bool,
+    pub data_compression: Vec<SqlDataCompressionOption>,
+    pub filegroup: Option<ObjectName>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlColumnStoreIndex {
+    pub name: ObjectName,
+    pub indexed_object: ObjectName,
+    /// Empty for clustered columnstore indexes, which cover the whole table
+    pub columns: Vec<ObjectName>,
+    pub filter_predicate: Option<String>,
+    pub is_clustered: bool,
+    pub is_disabled: bool,
+    pub data_compression: Vec<SqlDataCompressionOption>,
+    pub filegroup: Option<ObjectName>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlXmlIndex {
+    pub name: ObjectName,
+    pub indexed_object: ObjectName,
+    /// The `xml` column the index is built on
+    pub column: ObjectName,
+    pub is_primary: bool,
+    /// For secondary indexes, the primary XML index they extend
+    pub primary_xml_index: Option<ObjectName>,
+    /// For secondary indexes, `FOR VALUE`, `FOR PATH` or `FOR PROPERTY`
+    pub secondary_type: Option<SecondaryXmlIndexType>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlSpatialIndex {
+    pub name: ObjectName,
+    pub indexed_object: ObjectName,
+    /// The `geometry` or `geography` column the index is built on
+    pub column: ObjectName,
+    pub data_compression: Vec<SqlDataCompressionOption>,
+    pub filegroup: Option<ObjectName>,
+}
+
+/// The `DATA_COMPRESSION` of an index, for all partitions or a single one
+#[derive(Debug, PartialEq)]
+pub struct SqlDataCompressionOption {
+    pub level: DataCompression,
+    /// `None` when the option applies to all partitions
+    pub partition_number: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DataCompression {
+    #[default]
+    None,
+    Row,
+    Page,
+    ColumnStore,
+    ColumnStoreArchive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecondaryXmlIndexType {
+    Value,
+    Path,
+    Property,
+}
+
+impl TryFrom<&RawElement> for SqlIndex {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlIndex {
+            indexed_object: e.required_object_reference("IndexedObject")?,
+            column_specifications: column_specifications(e)?,
+            included_columns: e.object_references("IncludedColumns")?,
+            filter_predicate: e.property_value("FilterPredicate"),
+            is_unique: e.bool_property("IsUnique", false),
+            is_clustered: e.bool_property("IsClustered", false),
+            fill_factor: e.parse_property("FillFactor")?,
+            is_disabled: e.bool_property("IsDisabled", false),
+            data_compression: data_compression_options(e)?,
+            filegroup: e.object_reference("Filegroup")?,
+            name: e.object_name("Index")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlColumnStoreIndex {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlColumnStoreIndex {
+            indexed_object: e.required_object_reference("IndexedObject")?,
+            columns: e.object_references("Columns")?,
+            filter_predicate: e.property_value("FilterPredicate"),
+            is_clustered: e.bool_property("IsClustered", false),
+            is_disabled: e.bool_property("IsDisabled", false),
+            data_compression: data_compression_options(e)?,
+            filegroup: e.object_reference("Filegroup")?,
+            name: e.object_name("Index")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlXmlIndex {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlXmlIndex {
+            indexed_object: e.required_object_reference("IndexedObject")?,
+            column: e.required_object_reference("Columns")?,
+            is_primary: e.bool_property("IsPrimary", false),
+            primary_xml_index: e.object_reference("PrimaryXmlIndex")?,
+            secondary_type: e.parse_property("SecondaryXmlIndexType")?,
+            name: e.object_name("Index")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlSpatialIndex {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlSpatialIndex {
+            indexed_object: e.required_object_reference("IndexedObject")?,
+            column: e.required_object_reference("Columns")?,
+            data_compression: data_compression_options(e)?,
+            filegroup: e.object_reference("Filegroup")?,
+            name: e.object_name("Index")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlDataCompressionOption {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlDataCompressionOption {
+            level: e.parse_property("CompressionLevel")?.unwrap_or_default(),
+            partition_number: e.parse_property("PartitionNumber")?,
+        })
+    }
+}
+
+impl FromStr for DataCompression {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "0" | "None" => Ok(DataCompression::None),
+            "1" | "Row" => Ok(DataCompression::Row),
+            "2" | "Page" => Ok(DataCompression::Page),
+            "3" | "ColumnStore" => Ok(DataCompression::ColumnStore),
+            "4" | "ColumnStoreArchive" => Ok(DataCompression::ColumnStoreArchive),
+            _ => Err(anyhow!("Unknown data compression level `{s}`")),
+        }
+    }
+}
+
+impl FromStr for SecondaryXmlIndexType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "0" | "Value" => Ok(SecondaryXmlIndexType::Value),
+            "1" | "Path" => Ok(SecondaryXmlIndexType::Path),
+            "2" | "Property" => Ok(SecondaryXmlIndexType::Property),
+            _ => Err(anyhow!("Unknown secondary XML index type `{s}`")),
+        }
+    }
+}
+
+/// Reads the `DataCompressionOptions` relationship of an index
+fn data_compression_options(e: &RawElement) -> Result<Vec<SqlDataCompressionOption>> {
+    e.elements("DataCompressionOptions")
+        .into_iter()
+        .map(SqlDataCompressionOption::try_from)
+        .collect()
+}
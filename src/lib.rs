@@ -9,36 +9,81 @@
 //!    cargo run --example flattened_enum --features="serialize"
 
 use anyhow::Result;
-use core::{panic, str};
+use core::str;
 use simple::SimpleDacPacModel;
+use std::collections::HashMap;
 use std::fmt;
 use std::fs::File;
 use std::io::Read;
+use tracing::warn;
 
+use constraint::{
+    SqlCheckConstraint, SqlDefaultConstraint, SqlForeignKeyConstraint, SqlPrimaryKeyConstraint,
+    SqlUniqueConstraint,
+};
+use database::SqlDatabaseOptions;
+use element::RawElement;
+use external::{SqlExternalDataSource, SqlExternalFileFormat, SqlExternalTable};
+use fulltext::{SqlFullTextCatalog, SqlFullTextIndex, SqlFullTextStopList};
+use index::{SqlColumnStoreIndex, SqlIndex, SqlSpatialIndex, SqlXmlIndex};
+use name::ObjectName;
+use programmability::{
+    SqlDmlTrigger, SqlInlineTableValuedFunction, SqlMultiStatementTableValuedFunction,
+    SqlProcedure, SqlScalarFunction,
+};
 use quick_xml::de::from_str;
+use security::{SqlPermissionStatement, SqlRole, SqlRoleMembership, SqlSchema, SqlUser};
+use sequence::SqlSequence;
 use serde::de::value::MapAccessDeserializer;
 use serde::de::{Error, MapAccess, Visitor};
 use serde::Deserialize;
+use storage::{SqlFilegroup, SqlPartitionFunction, SqlPartitionScheme};
+use synonym::SqlSynonym;
 use table::SqlTable;
+use types::{SqlTableType, SqlUserDefinedDataType};
+use view::SqlView;
 
 pub mod bacpac;
+pub mod bcp;
+pub mod constraint;
+pub mod database;
+pub mod decimal;
+pub mod element;
+pub mod external;
+pub mod fulltext;
+pub mod index;
+pub mod name;
+pub mod programmability;
+pub mod security;
+pub mod sequence;
 pub mod simple;
+pub mod storage;
+pub mod synonym;
 pub mod table;
+pub mod temporal;
+pub mod types;
+pub mod view;
 
 /// Deserializes a DacPac `model.xml` from an XML string
-pub fn from_xml(xml: &str) -> DacPacModel {
-    let dsm: DacPacModel = from_str(xml).unwrap();
-    dsm
+pub fn from_xml(xml: &str) -> Result<DacPacModel> {
+    let dsm: DacPacModel = from_str(xml)?;
+    Ok(dsm)
 }
 
 pub fn from_dacpac_file(file: &File) -> Result<DacPacModel> {
-    let mut archive = zip::ZipArchive::new(file).unwrap();
+    let contents = read_model_xml(file)?;
+
+    from_xml(contents.as_str())
+}
+
+/// Reads the model.xml entry of a dacpac or bacpac
+pub(crate) fn read_model_xml(file: &File) -> Result<String> {
+    let mut archive = zip::ZipArchive::new(file)?;
     let mut model_xml = archive.by_name("model.xml")?;
 
     let mut contents = String::new();
-    model_xml.read_to_string(&mut contents).unwrap();
-
-    Ok(from_xml(contents.as_str()))
+    model_xml.read_to_string(&mut contents)?;
+    Ok(contents)
 }
 
 impl DacPacModel {
@@ -71,69 +116,83 @@ pub enum ElementEnum {
     SqlDatabaseOptions(SqlDatabaseOptions),
     SqlDefaultConstraint(SqlDefaultConstraint),
     SqlPrimaryKeyConstraint(SqlPrimaryKeyConstraint),
+    SqlForeignKeyConstraint(SqlForeignKeyConstraint),
     SqlRoleMembership(SqlRoleMembership),
     SqlUser(SqlUser),
     SqlTable(SqlTable),
     SqlView(SqlView),
     SqlUniqueConstraint(SqlUniqueConstraint),
+    SqlCheckConstraint(SqlCheckConstraint),
     SqlProcedure(SqlProcedure),
     SqlPermissionStatement(SqlPermissionStatement),
     SqlSchema(SqlSchema),
     SqlExternalFileFormat(SqlExternalFileFormat),
     SqlExternalDataSource(SqlExternalDataSource),
+    SqlExternalTable(SqlExternalTable),
+    SqlIndex(SqlIndex),
+    SqlColumnStoreIndex(SqlColumnStoreIndex),
+    SqlXmlIndex(SqlXmlIndex),
+    SqlSpatialIndex(SqlSpatialIndex),
+    SqlScalarFunction(SqlScalarFunction),
+    SqlInlineTableValuedFunction(SqlInlineTableValuedFunction),
+    SqlMultiStatementTableValuedFunction(SqlMultiStatementTableValuedFunction),
+    SqlDmlTrigger(SqlDmlTrigger),
+    SqlRole(SqlRole),
+    SqlSequence(SqlSequence),
+    SqlSynonym(SqlSynonym),
+    SqlUserDefinedDataType(SqlUserDefinedDataType),
+    SqlTableType(SqlTableType),
+    SqlFilegroup(SqlFilegroup),
+    SqlPartitionFunction(SqlPartitionFunction),
+    SqlPartitionScheme(SqlPartitionScheme),
+    SqlFullTextCatalog(SqlFullTextCatalog),
+    SqlFullTextIndex(SqlFullTextIndex),
+    SqlFullTextStopList(SqlFullTextStopList),
+    /// An element type this crate doesn't model yet, or an element that
+    /// failed to convert to its typed struct, kept as is
+    Unknown {
+        ty: String,
+        name: Option<String>,
+        raw: RawElement,
+        /// Why the element failed to convert, `None` when its type isn't modelled
+        error: Option<String>,
+    },
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-pub struct SqlDatabaseOptions {}
-
-#[derive(Debug, Deserialize, PartialEq)]
-pub struct SqlDefaultConstraint {}
-
-#[derive(Debug, Deserialize, PartialEq)]
-pub struct SqlPrimaryKeyConstraint {}
-
-#[derive(Debug, Deserialize, PartialEq)]
-pub struct SqlRoleMembership {}
-
-#[derive(Debug, Deserialize, PartialEq)]
-pub struct SqlUser {}
-
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct Property {
     #[serde(rename = "@Name")]
     pub name: String,
     #[serde(rename = "@Value")]
     pub value: Option<String>,
+    /// Scripts are written in a CDATA `Value` child instead of the attribute
+    #[serde(rename = "Value")]
+    pub cdata: Option<PropertyValue>,
+}
+
+/// The `Value` child of a `Property`
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PropertyValue {
+    #[serde(rename = "$text", default)]
+    pub text: String,
+    /// `SET QUOTED_IDENTIFIER` when the script was parsed, `None` when on
+    #[serde(rename = "@QuotedIdentifiers")]
+    pub quoted_identifiers: Option<String>,
+    /// `SET ANSI_NULLS` when the script was parsed, `None` when on
+    #[serde(rename = "@AnsiNulls")]
+    pub ansi_nulls: Option<String>,
 }
 
 impl Property {
     pub fn get_value(&self) -> String {
-        self.value.clone().unwrap_or_default()
+        self.value
+            .clone()
+            .or_else(|| self.cdata.as_ref().map(|v| v.text.clone()))
+            .unwrap_or_default()
     }
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-pub struct SqlView {}
-
-#[derive(Debug, Deserialize, PartialEq)]
-pub struct SqlUniqueConstraint {}
-
-#[derive(Debug, Deserialize, PartialEq)]
-pub struct SqlProcedure {}
-
-#[derive(Debug, Deserialize, PartialEq)]
-pub struct SqlPermissionStatement {}
-
-#[derive(Debug, Deserialize, PartialEq)]
-pub struct SqlSchema {}
-
-#[derive(Debug, Deserialize, PartialEq)]
-pub struct SqlExternalFileFormat {}
-
-#[derive(Debug, Deserialize, PartialEq)]
-pub struct SqlExternalDataSource {}
-
 impl<'de> Deserialize<'de> for ElementEnum {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -155,65 +214,9 @@ impl<'de> Deserialize<'de> for ElementEnum {
                 if let Some((key, value)) = map.next_entry::<String, String>()? {
                     return match key.as_str() {
                         "@Type" => {
-                            let value = value.as_str();
-                            let mad = MapAccessDeserializer::new(map);
-                            match value {
-                                "SqlDatabaseOptions" => {
-                                    let f = SqlDatabaseOptions::deserialize(mad)?;
-                                    Ok(ElementEnum::SqlDatabaseOptions(f))
-                                }
-                                "SqlDefaultConstraint" => {
-                                    let f = SqlDefaultConstraint::deserialize(mad)?;
-                                    Ok(ElementEnum::SqlDefaultConstraint(f))
-                                }
-                                "SqlPrimaryKeyConstraint" => {
-                                    let f = SqlPrimaryKeyConstraint::deserialize(mad)?;
-                                    Ok(ElementEnum::SqlPrimaryKeyConstraint(f))
-                                }
-                                "SqlRoleMembership" => {
-                                    let f = SqlRoleMembership::deserialize(mad)?;
-                                    Ok(ElementEnum::SqlRoleMembership(f))
-                                }
-                                "SqlUser" => {
-                                    let f = SqlUser::deserialize(mad)?;
-                                    Ok(ElementEnum::SqlUser(f))
-                                }
-                                "SqlTable" => {
-                                    let f = SqlTable::deserialize(mad)?;
-                                    Ok(ElementEnum::SqlTable(f))
-                                }
-                                "SqlView" => {
-                                    let f = SqlView::deserialize(mad)?;
-                                    Ok(ElementEnum::SqlView(f))
-                                }
-                                "SqlUniqueConstraint" => {
-                                    let f = SqlUniqueConstraint::deserialize(mad)?;
-                                    Ok(ElementEnum::SqlUniqueConstraint(f))
-                                }
-                                "SqlProcedure" => {
-                                    let f = SqlProcedure::deserialize(mad)?;
-                                    Ok(ElementEnum::SqlProcedure(f))
-                                }
-                                "SqlPermissionStatement" => {
-                                    let f = SqlPermissionStatement::deserialize(mad)?;
-                                    Ok(ElementEnum::SqlPermissionStatement(f))
-                                }
-                                "SqlSchema" => {
-                                    let f = SqlSchema::deserialize(mad)?;
-                                    Ok(ElementEnum::SqlSchema(f))
-                                }
-                                "SqlExternalFileFormat" => {
-                                    let f = SqlExternalFileFormat::deserialize(mad)?;
-                                    Ok(ElementEnum::SqlExternalFileFormat(f))
-                                }
-                                "SqlExternalDataSource" => {
-                                    let f = SqlExternalDataSource::deserialize(mad)?;
-                                    Ok(ElementEnum::SqlExternalDataSource(f))
-                                }
-                                _ => {
-                                    todo!("Unknown SQL type attribute `{}`", value);
-                                }
-                            }
+                            let mut raw = RawElement::deserialize(MapAccessDeserializer::new(map))?;
+                            raw.ty = value;
+                            Ok(typed_element(raw))
                         }
                         _ => Err(Error::custom(format!("unknown type attribute `{}`", key))),
                     };
@@ -225,21 +228,874 @@ impl<'de> Deserialize<'de> for ElementEnum {
     }
 }
 
+/// Converts an element into its typed struct. Elements of types this crate
+/// doesn't model, or that fail to convert, are kept as `Unknown`.
+fn typed_element(raw: RawElement) -> ElementEnum {
+    let converted = match raw.ty.as_str() {
+        "SqlDatabaseOptions" => (&raw).try_into().map(ElementEnum::SqlDatabaseOptions),
+        "SqlDefaultConstraint" => (&raw).try_into().map(ElementEnum::SqlDefaultConstraint),
+        "SqlPrimaryKeyConstraint" => (&raw).try_into().map(ElementEnum::SqlPrimaryKeyConstraint),
+        "SqlForeignKeyConstraint" => (&raw).try_into().map(ElementEnum::SqlForeignKeyConstraint),
+        "SqlRoleMembership" => (&raw).try_into().map(ElementEnum::SqlRoleMembership),
+        "SqlUser" => (&raw).try_into().map(ElementEnum::SqlUser),
+        "SqlTable" => (&raw).try_into().map(ElementEnum::SqlTable),
+        "SqlView" => (&raw).try_into().map(ElementEnum::SqlView),
+        "SqlUniqueConstraint" => (&raw).try_into().map(ElementEnum::SqlUniqueConstraint),
+        "SqlCheckConstraint" => (&raw).try_into().map(ElementEnum::SqlCheckConstraint),
+        "SqlProcedure" => (&raw).try_into().map(ElementEnum::SqlProcedure),
+        "SqlPermissionStatement" => (&raw).try_into().map(ElementEnum::SqlPermissionStatement),
+        "SqlSchema" => (&raw).try_into().map(ElementEnum::SqlSchema),
+        "SqlExternalFileFormat" => (&raw).try_into().map(ElementEnum::SqlExternalFileFormat),
+        "SqlExternalDataSource" => (&raw).try_into().map(ElementEnum::SqlExternalDataSource),
+        "SqlExternalTable" => (&raw).try_into().map(ElementEnum::SqlExternalTable),
+        "SqlIndex" => (&raw).try_into().map(ElementEnum::SqlIndex),
+        "SqlColumnStoreIndex" => (&raw).try_into().map(ElementEnum::SqlColumnStoreIndex),
+        "SqlXmlIndex" => (&raw).try_into().map(ElementEnum::SqlXmlIndex),
+        "SqlSpatialIndex" => (&raw).try_into().map(ElementEnum::SqlSpatialIndex),
+        "SqlScalarFunction" => (&raw).try_into().map(ElementEnum::SqlScalarFunction),
+        "SqlInlineTableValuedFunction" => (&raw)
+            .try_into()
+            .map(ElementEnum::SqlInlineTableValuedFunction),
+        "SqlMultiStatementTableValuedFunction" => (&raw)
+            .try_into()
+            .map(ElementEnum::SqlMultiStatementTableValuedFunction),
+        "SqlDmlTrigger" => (&raw).try_into().map(ElementEnum::SqlDmlTrigger),
+        "SqlRole" => (&raw).try_into().map(ElementEnum::SqlRole),
+        "SqlSequence" => (&raw).try_into().map(ElementEnum::SqlSequence),
+        "SqlSynonym" => (&raw).try_into().map(ElementEnum::SqlSynonym),
+        "SqlUserDefinedDataType" => (&raw).try_into().map(ElementEnum::SqlUserDefinedDataType),
+        "SqlTableType" => (&raw).try_into().map(ElementEnum::SqlTableType),
+        "SqlFilegroup" => (&raw).try_into().map(ElementEnum::SqlFilegroup),
+        "SqlPartitionFunction" => (&raw).try_into().map(ElementEnum::SqlPartitionFunction),
+        "SqlPartitionScheme" => (&raw).try_into().map(ElementEnum::SqlPartitionScheme),
+        "SqlFullTextCatalog" => (&raw).try_into().map(ElementEnum::SqlFullTextCatalog),
+        "SqlFullTextIndex" => (&raw).try_into().map(ElementEnum::SqlFullTextIndex),
+        "SqlFullTextStopList" => (&raw).try_into().map(ElementEnum::SqlFullTextStopList),
+        _ => return unknown_element(raw, None),
+    };
+    converted.unwrap_or_else(|e: anyhow::Error| {
+        warn!(
+            "Keeping {} {} as an unknown element: {e:#}",
+            raw.ty,
+            raw.name.as_deref().unwrap_or_default()
+        );
+        unknown_element(raw, Some(format!("{e:#}")))
+    })
+}
+
+fn unknown_element(raw: RawElement, error: Option<String>) -> ElementEnum {
+    ElementEnum::Unknown {
+        ty: raw.ty.clone(),
+        name: raw.name.clone(),
+        raw,
+        error,
+    }
+}
+
 impl From<&DacPacModel> for simple::SimpleDacPacModel {
     fn from(dm: &DacPacModel) -> Self {
         let mut tables: Vec<simple::SimpleTable> = Vec::new();
-        //let mut views: Vec<simple::SimpleView> = Vec::new();
+        let mut views: Vec<simple::SimpleView> = Vec::new();
+        let mut table_types: Vec<simple::SimpleTable> = Vec::new();
+        let mut user_defined_types: Vec<simple::SimpleUserDefinedType> = Vec::new();
+        let mut sequences: Vec<simple::SimpleSequence> = Vec::new();
+        let mut synonyms: Vec<simple::SimpleSynonym> = Vec::new();
+        let mut filegroups: Vec<simple::SimpleFilegroup> = Vec::new();
+        let mut partition_functions: Vec<simple::SimplePartitionFunction> = Vec::new();
+        let mut partition_schemes: Vec<simple::SimplePartitionScheme> = Vec::new();
+        let mut fulltext_catalogs: Vec<simple::SimpleFullTextCatalog> = Vec::new();
+        let mut fulltext_stop_lists: Vec<simple::SimpleFullTextStopList> = Vec::new();
+        let mut external_data_sources: Vec<simple::SimpleExternalDataSource> = Vec::new();
+        let mut external_file_formats: Vec<simple::SimpleExternalFileFormat> = Vec::new();
+        let mut external_tables: Vec<simple::SimpleExternalTable> = Vec::new();
+        let mut procedures: Vec<simple::SimpleProcedure> = Vec::new();
+        let mut functions: Vec<simple::SimpleFunction> = Vec::new();
+        let mut triggers: Vec<simple::SimpleTrigger> = Vec::new();
+        let mut security = simple::SimpleSecurityModel::default();
+        let mut options: Option<simple::SimpleDatabaseOptions> = None;
+        let mut unconverted = Vec::new();
 
         for e in &dm.model.element {
-            #[allow(clippy::single_match)]
             match e {
                 ElementEnum::SqlTable(t) => {
                     tables.push(simple::SimpleTable::from(t));
                 }
+                ElementEnum::SqlView(v) => {
+                    views.push(simple::SimpleView::from(v));
+                }
+                ElementEnum::SqlTableType(t) => {
+                    table_types.push(simple::SimpleTable::from(t));
+                }
+                ElementEnum::SqlUserDefinedDataType(t) => {
+                    user_defined_types.push(simple::SimpleUserDefinedType::from(t));
+                }
+                ElementEnum::SqlSequence(s) => {
+                    sequences.push(simple::SimpleSequence::from(s));
+                }
+                ElementEnum::SqlSynonym(s) => {
+                    synonyms.push(simple::SimpleSynonym::from(s));
+                }
+                ElementEnum::SqlFilegroup(f) => {
+                    filegroups.push(simple::SimpleFilegroup::from(f));
+                }
+                ElementEnum::SqlPartitionFunction(f) => {
+                    partition_functions.push(simple::SimplePartitionFunction::from(f));
+                }
+                ElementEnum::SqlPartitionScheme(s) => {
+                    partition_schemes.push(simple::SimplePartitionScheme::from(s));
+                }
+                ElementEnum::SqlFullTextCatalog(c) => {
+                    fulltext_catalogs.push(simple::SimpleFullTextCatalog::from(c));
+                }
+                ElementEnum::SqlFullTextStopList(sl) => {
+                    fulltext_stop_lists.push(simple::SimpleFullTextStopList::from(sl));
+                }
+                ElementEnum::SqlExternalDataSource(ds) => {
+                    external_data_sources.push(simple::SimpleExternalDataSource::from(ds));
+                }
+                ElementEnum::SqlExternalFileFormat(ff) => {
+                    external_file_formats.push(simple::SimpleExternalFileFormat::from(ff));
+                }
+                ElementEnum::SqlExternalTable(et) => {
+                    external_tables.push(simple::SimpleExternalTable::from(et));
+                }
+                ElementEnum::SqlProcedure(p) => {
+                    procedures.push(simple::SimpleProcedure::from(p));
+                }
+                ElementEnum::SqlScalarFunction(f) => {
+                    functions.push(simple::SimpleFunction::from(f));
+                }
+                ElementEnum::SqlInlineTableValuedFunction(f) => {
+                    functions.push(simple::SimpleFunction::from(f));
+                }
+                ElementEnum::SqlMultiStatementTableValuedFunction(f) => {
+                    functions.push(simple::SimpleFunction::from(f));
+                }
+                ElementEnum::SqlDmlTrigger(t) => {
+                    triggers.push(simple::SimpleTrigger::from(t));
+                }
+                ElementEnum::SqlDatabaseOptions(o) => {
+                    options = Some(simple::SimpleDatabaseOptions::from(o));
+                }
+                ElementEnum::SqlSchema(s) => {
+                    security.schemas.push(simple::SimpleSchema::from(s));
+                }
+                ElementEnum::SqlUser(u) => {
+                    security.users.push(simple::SimpleUser::from(u));
+                }
+                ElementEnum::SqlRole(r) => {
+                    security.roles.push(simple::SimpleRole::from(r));
+                }
+                ElementEnum::SqlRoleMembership(rm) => {
+                    security
+                        .role_memberships
+                        .push(simple::SimpleRoleMembership::from(rm));
+                }
+                ElementEnum::SqlPermissionStatement(ps) => {
+                    security
+                        .permissions
+                        .push(simple::SimplePermission::from(ps));
+                }
+                ElementEnum::Unknown {
+                    ty,
+                    name,
+                    error: Some(error),
+                    ..
+                } => {
+                    unconverted.push(simple::SimpleUnconvertedElement {
+                        ty: ty.clone(),
+                        name: name.clone(),
+                        error: error.clone(),
+                    });
+                }
                 _ => {}
             }
         }
-        SimpleDacPacModel { tables }
+
+        // Computed columns whose expression gives their type
+        for table in &mut tables {
+            let types: Vec<_> = table
+                .columns
+                .iter()
+                .map(|c| match (&c.ty, &c.computed) {
+                    (None, Some(computed)) => {
+                        computed_column_type(&computed.expression, &table.columns)
+                    }
+                    (ty, _) => ty.clone(),
+                })
+                .collect();
+            for (column, ty) in table.columns.iter_mut().zip(types) {
+                column.ty = ty;
+            }
+        }
+
+        // Alias types are only known once all elements are read
+        let aliases: HashMap<ObjectName, simple::SimpleColumnType> = user_defined_types
+            .iter()
+            .map(|t| (t.name.clone(), t.base.clone()))
+            .collect();
+        for column in tables
+            .iter_mut()
+            .chain(table_types.iter_mut())
+            .flat_map(|t| t.columns.iter_mut())
+            .chain(
+                external_tables
+                    .iter_mut()
+                    .flat_map(|t| t.columns.iter_mut()),
+            )
+        {
+            if let Some(ty) = &mut column.ty {
+                column.user_type = resolve_alias(ty, &aliases);
+            }
+        }
+        for parameter in procedures
+            .iter_mut()
+            .flat_map(|p| p.parameters.iter_mut())
+            .chain(functions.iter_mut().flat_map(|f| f.parameters.iter_mut()))
+        {
+            resolve_alias(&mut parameter.ty, &aliases);
+        }
+        for function in &mut functions {
+            if let simple::SimpleFunctionKind::Scalar(ty) = &mut function.kind {
+                resolve_alias(ty, &aliases);
+            }
+        }
+        for function in &mut partition_functions {
+            resolve_alias(&mut function.parameter_type, &aliases);
+        }
+        for sequence in &mut sequences {
+            resolve_alias(&mut sequence.ty, &aliases);
+        }
+
+        // View columns built from a single table column are given its type
+        for column in views.iter_mut().flat_map(|v| v.columns.iter_mut()) {
+            if let [source] = column.sources.as_slice() {
+                column.ty = find_column_type(&tables, source);
+            }
+        }
+
+        // Constraints and indexes reference their table, attach them once all
+        // tables are known
+        for e in &dm.model.element {
+            match e {
+                ElementEnum::SqlPrimaryKeyConstraint(pk) => {
+                    if let Some(table) =
+                        constraint_table(&mut tables, &pk.defining_table, "primary key")
+                    {
+                        table.primary_key = Some(simple::SimplePrimaryKey::from(pk));
+                    }
+                }
+                ElementEnum::SqlForeignKeyConstraint(fk) => {
+                    if let Some(table) =
+                        constraint_table(&mut tables, &fk.defining_table, "foreign key")
+                    {
+                        table.foreign_keys.push(simple::SimpleForeignKey::from(fk));
+                    }
+                }
+                ElementEnum::SqlUniqueConstraint(uq) => {
+                    if let Some(table) =
+                        constraint_table(&mut tables, &uq.defining_table, "unique constraint")
+                    {
+                        table
+                            .unique_constraints
+                            .push(simple::SimpleUniqueConstraint::from(uq));
+                    }
+                }
+                ElementEnum::SqlCheckConstraint(ck) => {
+                    if let Some(table) =
+                        constraint_table(&mut tables, &ck.defining_table, "check constraint")
+                    {
+                        table
+                            .check_constraints
+                            .push(simple::SimpleCheckConstraint::from(ck));
+                    }
+                }
+                ElementEnum::SqlIndex(ix) => {
+                    let index = simple::SimpleIndex::from(ix);
+                    attach_index(&mut tables, &mut views, &ix.indexed_object, index);
+                }
+                ElementEnum::SqlColumnStoreIndex(ix) => {
+                    let index = simple::SimpleIndex::from(ix);
+                    attach_index(&mut tables, &mut views, &ix.indexed_object, index);
+                }
+                ElementEnum::SqlXmlIndex(ix) => {
+                    let index = simple::SimpleIndex::from(ix);
+                    attach_index(&mut tables, &mut views, &ix.indexed_object, index);
+                }
+                ElementEnum::SqlSpatialIndex(ix) => {
+                    let index = simple::SimpleIndex::from(ix);
+                    attach_index(&mut tables, &mut views, &ix.indexed_object, index);
+                }
+                ElementEnum::SqlFullTextIndex(ix) => {
+                    let index = Some(simple::SimpleFullTextIndex::from(ix));
+                    if let Some(table) = find_table(&mut tables, &ix.indexed_object) {
+                        table.fulltext_index = index;
+                    } else if let Some(view) = find_view(&mut views, &ix.indexed_object) {
+                        view.fulltext_index = index;
+                    } else {
+                        warn!(
+                            "Dropping fulltext index on unknown object {}",
+                            ix.indexed_object
+                        );
+                    }
+                }
+                ElementEnum::SqlDefaultConstraint(df) => {
+                    let column_name = df.for_column.leaf();
+                    if let Some(column) = find_table(&mut tables, &df.defining_table)
+                        .and_then(|t| t.columns.iter_mut().find(|c| c.name == column_name))
+                    {
+                        column.default = Some(simple::SimpleDefault::from(df));
+                    } else {
+                        warn!(
+                            "Dropping default on unknown column {}.[{column_name}]",
+                            df.defining_table
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+        SimpleDacPacModel {
+            tables,
+            views,
+            table_types,
+            user_defined_types,
+            sequences,
+            synonyms,
+            filegroups,
+            partition_functions,
+            partition_schemes,
+            fulltext_catalogs,
+            fulltext_stop_lists,
+            external_data_sources,
+            external_file_formats,
+            external_tables,
+            procedures,
+            functions,
+            triggers,
+            security,
+            options,
+            unconverted,
+        }
+    }
+}
+
+/// Replaces an alias type by its base type, returning the alias name
+fn resolve_alias(
+    ty: &mut simple::SimpleColumnType,
+    aliases: &HashMap<ObjectName, simple::SimpleColumnType>,
+) -> Option<ObjectName> {
+    if let simple::SimpleColumnType::Other(name) = ty {
+        if let Some(base) = aliases.get(name) {
+            let alias = name.clone();
+            *ty = base.clone();
+            return Some(alias);
+        }
+    }
+    None
+}
+
+/// Infers the type of a computed column from a top-level `CAST`/`CONVERT` of
+/// its expression, or from the column it copies
+fn computed_column_type(
+    expression: &str,
+    columns: &[simple::SimpleTableColumn],
+) -> Option<simple::SimpleColumnType> {
+    let mut expression = expression.trim();
+    while let Some(inner) = expression
+        .strip_prefix('(')
+        .and_then(|e| e.strip_suffix(')'))
+        .filter(|e| closes_at_end(e))
+    {
+        expression = inner.trim();
+    }
+
+    let (function, args) = match expression.find('(') {
+        Some(i)
+            if expression.ends_with(')')
+                && closes_at_end(&expression[i + 1..expression.len() - 1]) =>
+        {
+            (&expression[..i], &expression[i + 1..expression.len() - 1])
+        }
+        _ => {
+            let name = name::parse_multipart(expression).ok()?;
+            let [name] = name.as_slice() else {
+                return None;
+            };
+            return columns.iter().find(|c| c.name == *name)?.ty.clone();
+        }
+    };
+
+    // Only ASCII bytes are looked for, so the indices are char boundaries
+    let bytes = args.as_bytes();
+    let ty = match function.trim().to_ascii_uppercase().as_str() {
+        "CAST" | "TRY_CAST" => {
+            let is_as = |i: usize| {
+                bytes[i..].len() > 3
+                    && bytes[i..i + 2].eq_ignore_ascii_case(b"AS")
+                    && matches!(bytes[i - 1], b' ' | b'\t' | b'\r' | b'\n' | b']' | b')')
+                    && matches!(bytes[i + 2], b' ' | b'\t' | b'\r' | b'\n' | b'[')
+                    && closes_at_end(&args[..i])
+            };
+            let at = (1..bytes.len()).rev().find(|i| is_as(*i))?;
+            &args[at + 2..]
+        }
+        "CONVERT" | "TRY_CONVERT" => {
+            let end = (0..bytes.len()).find(|i| bytes[*i] == b',' && closes_at_end(&args[..*i]))?;
+            &args[..end]
+        }
+        _ => return None,
+    };
+    let ty: table::ElementTypeSpecifier = ty.parse().ok()?;
+
+    // Without a length, CAST and CONVERT give 30 characters or bytes
+    use simple::SimpleColumnLength::{Bounded, Unspecified};
+    use simple::SimpleColumnType as T;
+    Some(match simple::SimpleColumnType::from(&ty) {
+        T::Char(Unspecified) => T::Char(Bounded(30)),
+        T::Varchar(Unspecified) => T::Varchar(Bounded(30)),
+        T::Nchar(Unspecified) => T::Nchar(Bounded(30)),
+        T::Nvarchar(Unspecified) => T::Nvarchar(Bounded(30)),
+        T::Binary(Unspecified) => T::Binary(Bounded(30)),
+        T::Varbinary(Unspecified) => T::Varbinary(Bounded(30)),
+        other => other,
+    })
+}
+
+/// Whether the parentheses of `s` are balanced, ignoring the ones in quotes
+fn closes_at_end(s: &str) -> bool {
+    let mut depth = 0i32;
+    let mut quote = None;
+    for c in s.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '[') => quote = Some(']'),
+            (None, '(') => depth += 1,
+            (None, ')') => {
+                depth -= 1;
+                if depth < 0 {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+    }
+    depth == 0 && quote.is_none()
+}
+
+/// Finds the simple table built from the `SqlTable` named `name`
+fn find_table<'a>(
+    tables: &'a mut [simple::SimpleTable],
+    name: &ObjectName,
+) -> Option<&'a mut simple::SimpleTable> {
+    tables.iter_mut().find(|t| t.name == *name)
+}
+
+/// Like [`find_table`], warning when the table a constraint is defined on is missing
+fn constraint_table<'a>(
+    tables: &'a mut [simple::SimpleTable],
+    name: &ObjectName,
+    kind: &str,
+) -> Option<&'a mut simple::SimpleTable> {
+    let table = find_table(tables, name);
+    if table.is_none() {
+        warn!("Dropping {kind} on unknown table {name}");
+    }
+    table
+}
+
+/// Finds the simple view built from the `SqlView` named `name`
+fn find_view<'a>(
+    views: &'a mut [simple::SimpleView],
+    name: &ObjectName,
+) -> Option<&'a mut simple::SimpleView> {
+    views.iter_mut().find(|v| v.name == *name)
+}
+
+/// Adds an index to the table or indexed view it is defined on
+fn attach_index(
+    tables: &mut [simple::SimpleTable],
+    views: &mut [simple::SimpleView],
+    indexed_object: &ObjectName,
+    index: simple::SimpleIndex,
+) {
+    if let Some(table) = find_table(tables, indexed_object) {
+        table.indexes.push(index);
+    } else if let Some(view) = find_view(views, indexed_object) {
+        view.indexes.push(index);
+    } else {
+        warn!(
+            "Dropping index {} on unknown object {indexed_object}",
+            index.name
+        );
+    }
+}
+
+/// Returns the type of the table column named `name`, i.e. `schema.table.column`
+fn find_column_type(
+    tables: &[simple::SimpleTable],
+    name: &ObjectName,
+) -> Option<simple::SimpleColumnType> {
+    let column = name.sub.as_deref()?;
+    let table = name.parent();
+    tables
+        .iter()
+        .find(|t| t.name == table)?
+        .columns
+        .iter()
+        .find(|c| c.name == column)?
+        .ty
+        .clone()
+}
+
+impl From<&SqlView> for simple::SimpleView {
+    fn from(sv: &SqlView) -> Self {
+        simple::SimpleView {
+            name: sv.name.clone(),
+            definition: sv.query_script.clone(),
+            schema_bound: sv.is_schema_bound,
+            with_check_option: sv.with_check_option,
+            columns: sv
+                .columns
+                .iter()
+                .map(|c| simple::SimpleViewColumn {
+                    name: c.name.leaf().to_string(),
+                    sources: c.dependencies.clone(),
+                    ty: None,
+                })
+                .collect(),
+            dependencies: sv.query_dependencies.clone(),
+            indexes: Vec::new(),
+            fulltext_index: None,
+        }
+    }
+}
+
+impl From<&SqlTableType> for simple::SimpleTable {
+    fn from(tt: &SqlTableType) -> Self {
+        let mut table = simple::SimpleTable {
+            name: tt.name.clone(),
+            columns: tt
+                .columns
+                .iter()
+                .map(|c| match c {
+                    table::SqlTableColumnElement::Simple(c) => simple::SimpleTableColumn::from(c),
+                    table::SqlTableColumnElement::Computed(c) => simple::SimpleTableColumn::from(c),
+                })
+                .collect(),
+            primary_key: None,
+            foreign_keys: Vec::new(),
+            unique_constraints: Vec::new(),
+            check_constraints: Vec::new(),
+            indexes: Vec::new(),
+            storage: simple::SimpleStorage::DefaultFilegroup,
+            text_image_filegroup: None,
+            fulltext_index: None,
+        };
+
+        for constraint in &tt.constraints {
+            match constraint {
+                types::SqlTableTypeConstraint::PrimaryKey {
+                    column_specifications,
+                    is_clustered,
+                } => {
+                    table.primary_key = Some(simple::SimplePrimaryKey {
+                        name: None,
+                        columns: column_specifications
+                            .iter()
+                            .map(simple::SimpleIndexColumn::from)
+                            .collect(),
+                        clustered: *is_clustered,
+                        fill_factor: None,
+                    });
+                }
+                types::SqlTableTypeConstraint::Unique {
+                    column_specifications,
+                    is_clustered,
+                } => {
+                    table
+                        .unique_constraints
+                        .push(simple::SimpleUniqueConstraint {
+                            name: None,
+                            columns: column_specifications
+                                .iter()
+                                .map(simple::SimpleIndexColumn::from)
+                                .collect(),
+                            clustered: *is_clustered,
+                            filegroup: None,
+                        });
+                }
+                types::SqlTableTypeConstraint::Check { expression } => {
+                    table.check_constraints.push(simple::SimpleCheckConstraint {
+                        name: None,
+                        expression: expression.clone(),
+                        not_for_replication: false,
+                        checked: true,
+                    });
+                }
+                types::SqlTableTypeConstraint::Default {
+                    for_column,
+                    expression,
+                } => {
+                    let column_name = for_column.leaf();
+                    if let Some(column) = table.columns.iter_mut().find(|c| c.name == column_name) {
+                        column.default = Some(simple::SimpleDefault {
+                            name: None,
+                            expression: expression.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        table
+    }
+}
+
+impl From<&SqlUserDefinedDataType> for simple::SimpleUserDefinedType {
+    fn from(ut: &SqlUserDefinedDataType) -> Self {
+        simple::SimpleUserDefinedType {
+            name: ut.name.clone(),
+            base: simple::SimpleColumnType::from(&ut.base_type),
+            nullable: ut.is_nullable,
+        }
+    }
+}
+
+impl From<&SqlSequence> for simple::SimpleSequence {
+    fn from(sq: &SqlSequence) -> Self {
+        simple::SimpleSequence {
+            name: sq.name.clone(),
+            ty: sq.type_specifier.as_ref().map_or(
+                simple::SimpleColumnType::BigInt,
+                simple::SimpleColumnType::from,
+            ),
+            start: sq.start_value,
+            increment: sq.increment_value,
+            min: sq.min_value,
+            max: sq.max_value,
+            cycle: sq.is_cycling,
+            cached: sq.is_cached,
+            cache_size: sq.cache_size,
+        }
+    }
+}
+
+impl From<&SqlSynonym> for simple::SimpleSynonym {
+    fn from(sy: &SqlSynonym) -> Self {
+        simple::SimpleSynonym {
+            name: sy.name.clone(),
+            target: sy.for_object.clone(),
+        }
+    }
+}
+
+impl From<&SqlFilegroup> for simple::SimpleFilegroup {
+    fn from(fg: &SqlFilegroup) -> Self {
+        simple::SimpleFilegroup {
+            name: fg.name.name.clone(),
+            memory_optimized: fg.contains_memory_optimized_data,
+            filestream: fg.contains_file_stream,
+        }
+    }
+}
+
+impl From<&SqlPartitionFunction> for simple::SimplePartitionFunction {
+    fn from(pf: &SqlPartitionFunction) -> Self {
+        simple::SimplePartitionFunction {
+            name: pf.name.name.clone(),
+            parameter_type: simple::SimpleColumnType::from(&pf.parameter_type),
+            range: pf.range,
+            boundaries: pf.boundary_values.clone(),
+        }
+    }
+}
+
+impl From<&SqlPartitionScheme> for simple::SimplePartitionScheme {
+    fn from(ps: &SqlPartitionScheme) -> Self {
+        simple::SimplePartitionScheme {
+            name: ps.name.name.clone(),
+            function: ps.partition_function.name.clone(),
+            filegroups: ps.filegroups.iter().map(|f| f.name.clone()).collect(),
+        }
+    }
+}
+
+impl From<&SqlProcedure> for simple::SimpleProcedure {
+    fn from(sp: &SqlProcedure) -> Self {
+        simple::SimpleProcedure {
+            name: sp.name.clone(),
+            parameters: simple_parameters(&sp.parameters),
+            body: sp.body_script.clone(),
+            execute_as: sp.execute_as.clone(),
+        }
+    }
+}
+
+impl From<&SqlScalarFunction> for simple::SimpleFunction {
+    fn from(sf: &SqlScalarFunction) -> Self {
+        simple::SimpleFunction {
+            name: sf.name.clone(),
+            kind: simple::SimpleFunctionKind::Scalar(simple::SimpleColumnType::from(
+                &sf.return_type,
+            )),
+            parameters: simple_parameters(&sf.parameters),
+            body: sf.body_script.clone(),
+            execute_as: sf.execute_as.clone(),
+        }
+    }
+}
+
+impl From<&SqlInlineTableValuedFunction> for simple::SimpleFunction {
+    fn from(sf: &SqlInlineTableValuedFunction) -> Self {
+        simple::SimpleFunction {
+            name: sf.name.clone(),
+            kind: simple::SimpleFunctionKind::InlineTableValued,
+            parameters: simple_parameters(&sf.parameters),
+            body: sf.body_script.clone(),
+            execute_as: None,
+        }
+    }
+}
+
+impl From<&SqlMultiStatementTableValuedFunction> for simple::SimpleFunction {
+    fn from(sf: &SqlMultiStatementTableValuedFunction) -> Self {
+        simple::SimpleFunction {
+            name: sf.name.clone(),
+            kind: simple::SimpleFunctionKind::MultiStatementTableValued,
+            parameters: simple_parameters(&sf.parameters),
+            body: sf.body_script.clone(),
+            execute_as: sf.execute_as.clone(),
+        }
+    }
+}
+
+impl From<&SqlDmlTrigger> for simple::SimpleTrigger {
+    fn from(st: &SqlDmlTrigger) -> Self {
+        simple::SimpleTrigger {
+            name: st.name.clone(),
+            table: st.parent.clone(),
+            timing: st.timing,
+            on_insert: st.is_insert_trigger,
+            on_update: st.is_update_trigger,
+            on_delete: st.is_delete_trigger,
+            not_for_replication: st.is_not_for_replication,
+            body: st.body_script.clone(),
+            execute_as: st.execute_as.clone(),
+        }
+    }
+}
+
+fn simple_parameters(
+    parameters: &[programmability::SqlSubroutineParameter],
+) -> Vec<simple::SimpleParameter> {
+    parameters
+        .iter()
+        .map(|p| simple::SimpleParameter {
+            name: p.name.leaf().to_string(),
+            type_name: p.type_specifier.type_name.clone(),
+            ty: simple::SimpleColumnType::from(&p.type_specifier),
+            direction: p.direction,
+            default: p.default_expression.clone(),
+            read_only: p.is_read_only,
+        })
+        .collect()
+}
+
+impl From<&SqlDatabaseOptions> for simple::SimpleDatabaseOptions {
+    fn from(o: &SqlDatabaseOptions) -> Self {
+        simple::SimpleDatabaseOptions {
+            collation: o.collation.clone(),
+            compatibility_level: o.compatibility_level,
+            recovery_model: o.recovery_mode,
+            containment: o.containment,
+            page_verify: o.page_verify_mode,
+            read_committed_snapshot: o.is_read_committed_snapshot_on,
+            allow_snapshot_isolation: o.allow_snapshot_isolation,
+            memory_optimized_elevate_to_snapshot: o.is_memory_optimized_elevated_to_snapshot,
+            ansi_null_default: o.is_ansi_null_default_on,
+            ansi_nulls: o.is_ansi_nulls_on,
+            ansi_padding: o.is_ansi_padding_on,
+            ansi_warnings: o.is_ansi_warnings_on,
+            arithabort: o.is_arith_abort_on,
+            concat_null_yields_null: o.is_concat_null_yields_null_on,
+            numeric_roundabort: o.is_numeric_round_abort_on,
+            quoted_identifier: o.is_quoted_identifier_on,
+            recursive_triggers: o.is_recursive_triggers_on,
+            auto_close: o.is_auto_close_on,
+            auto_shrink: o.is_auto_shrink_on,
+            auto_create_statistics: o.is_auto_create_statistics_on,
+            auto_update_statistics: o.is_auto_update_statistics_on,
+            auto_update_statistics_async: o.is_auto_update_statistics_async_on,
+            trustworthy: o.is_trustworthy,
+            db_chaining: o.is_db_chaining_on,
+            full_text_enabled: o.is_full_text_enabled,
+            default_language: o.default_language.clone(),
+            default_full_text_language: o.default_full_text_language.clone(),
+            other: o
+                .properties
+                .iter()
+                .filter(|p| !database::TYPED_PROPERTIES.contains(&p.name.as_str()))
+                .map(|p| (p.name.clone(), p.get_value()))
+                .collect(),
+        }
+    }
+}
+
+impl From<&SqlSchema> for simple::SimpleSchema {
+    fn from(ss: &SqlSchema) -> Self {
+        simple::SimpleSchema {
+            name: ss.name.name.clone(),
+            owner: ss
+                .authorizer
+                .as_ref()
+                .map_or_else(|| "dbo".to_string(), |a| a.name.clone()),
+        }
+    }
+}
+
+impl From<&SqlUser> for simple::SimpleUser {
+    fn from(su: &SqlUser) -> Self {
+        simple::SimpleUser {
+            name: su.name.name.clone(),
+            login: su.login.as_ref().map(|n| n.name.clone()),
+            authentication: su.authentication_type,
+            default_schema: su.default_schema.clone(),
+            without_login: su.is_without_login,
+        }
+    }
+}
+
+impl From<&SqlRole> for simple::SimpleRole {
+    fn from(sr: &SqlRole) -> Self {
+        simple::SimpleRole {
+            name: sr.name.name.clone(),
+            owner: sr
+                .authorizer
+                .as_ref()
+                .map_or_else(|| "dbo".to_string(), |a| a.name.clone()),
+        }
+    }
+}
+
+impl From<&SqlRoleMembership> for simple::SimpleRoleMembership {
+    fn from(rm: &SqlRoleMembership) -> Self {
+        simple::SimpleRoleMembership {
+            role: rm.role.name.clone(),
+            member: rm.member.name.clone(),
+        }
+    }
+}
+
+impl From<&SqlPermissionStatement> for simple::SimplePermission {
+    fn from(ps: &SqlPermissionStatement) -> Self {
+        simple::SimplePermission {
+            action: ps.action,
+            permission: ps.permission.clone(),
+            grantee: ps.grantee.name.clone(),
+            securable: ps.secured_object.clone(),
+        }
     }
 }
 
@@ -248,73 +1104,729 @@ impl From<&SqlTable> for simple::SimpleTable {
         let mut columns: Vec<simple::SimpleTableColumn> = Vec::new();
 
         for column in &st.columns_relationship.entry {
-            columns.push(simple::SimpleTableColumn::from(&column.element));
+            columns.push(match &column.element {
+                table::SqlTableColumnElement::Simple(c) => simple::SimpleTableColumn::from(c),
+                table::SqlTableColumnElement::Computed(c) => simple::SimpleTableColumn::from(c),
+            });
         }
 
+        if let (Some(scheme), None) = (&st.partition_scheme, &st.partition_column) {
+            warn!(
+                "Table {} is on partition scheme {scheme} but has no partition column",
+                st.name
+            );
+        }
+        let storage = match (&st.partition_scheme, &st.partition_column, &st.filegroup) {
+            (Some(scheme), Some(column), _) => simple::SimpleStorage::PartitionScheme {
+                scheme: scheme.name.clone(),
+                column: column.leaf().to_string(),
+            },
+            (_, _, Some(filegroup)) => simple::SimpleStorage::Filegroup(filegroup.name.clone()),
+            _ => simple::SimpleStorage::DefaultFilegroup,
+        };
+
         simple::SimpleTable {
-            name: simple::remove_delimiters(&st.name),
+            name: st.name.clone(),
             columns,
+            primary_key: None,
+            foreign_keys: Vec::new(),
+            unique_constraints: Vec::new(),
+            check_constraints: Vec::new(),
+            indexes: Vec::new(),
+            storage,
+            text_image_filegroup: st.text_image_filegroup.as_ref().map(|n| n.name.clone()),
+            fulltext_index: None,
+        }
+    }
+}
+
+impl From<&SqlExternalDataSource> for simple::SimpleExternalDataSource {
+    fn from(ds: &SqlExternalDataSource) -> Self {
+        simple::SimpleExternalDataSource {
+            name: ds.name.name.clone(),
+            ty: ds.data_source_type,
+            location: ds.location.clone(),
+            credential: ds.credential.as_ref().map(|n| n.name.clone()),
+            resource_manager_location: ds.resource_manager_location.clone(),
+            database_name: ds.database_name.clone(),
+            shard_map_name: ds.shard_map_name.clone(),
+        }
+    }
+}
+
+impl From<&SqlExternalFileFormat> for simple::SimpleExternalFileFormat {
+    fn from(ff: &SqlExternalFileFormat) -> Self {
+        simple::SimpleExternalFileFormat {
+            name: ff.name.name.clone(),
+            ty: ff.format_type,
+            field_terminator: ff.field_terminator.clone(),
+            string_delimiter: ff.string_delimiter.clone(),
+            date_format: ff.date_format.clone(),
+            use_type_default: ff.use_type_default,
+            first_row: ff.first_row,
+            encoding: ff.encoding,
+            compression: ff.data_compression.clone(),
+        }
+    }
+}
+
+impl From<&SqlExternalTable> for simple::SimpleExternalTable {
+    fn from(et: &SqlExternalTable) -> Self {
+        simple::SimpleExternalTable {
+            name: et.name.clone(),
+            columns: et
+                .columns
+                .iter()
+                .map(|c| match c {
+                    table::SqlTableColumnElement::Simple(c) => simple::SimpleTableColumn::from(c),
+                    table::SqlTableColumnElement::Computed(c) => simple::SimpleTableColumn::from(c),
+                })
+                .collect(),
+            data_source: et.data_source.name.clone(),
+            file_format: et.file_format.as_ref().map(|n| n.name.clone()),
+            location: et.location.clone(),
+            reject: et.reject_value.map(|value| simple::SimpleRejectOptions {
+                ty: et.reject_type,
+                value,
+                sample_value: et.reject_sample_value,
+                rejected_row_location: et.rejected_row_location.clone(),
+            }),
+        }
+    }
+}
+
+impl From<&SqlPrimaryKeyConstraint> for simple::SimplePrimaryKey {
+    fn from(pk: &SqlPrimaryKeyConstraint) -> Self {
+        simple::SimplePrimaryKey {
+            name: pk.name.clone(),
+            columns: pk
+                .column_specifications
+                .iter()
+                .map(simple::SimpleIndexColumn::from)
+                .collect(),
+            clustered: pk.is_clustered,
+            fill_factor: pk.fill_factor,
+        }
+    }
+}
+
+impl From<&SqlForeignKeyConstraint> for simple::SimpleForeignKey {
+    fn from(fk: &SqlForeignKeyConstraint) -> Self {
+        simple::SimpleForeignKey {
+            name: fk.name.clone(),
+            columns: fk.columns.iter().map(|c| c.leaf().to_string()).collect(),
+            foreign_table: fk.foreign_table.clone(),
+            foreign_columns: fk
+                .foreign_columns
+                .iter()
+                .map(|c| c.leaf().to_string())
+                .collect(),
+            on_delete: fk.delete_action,
+            on_update: fk.update_action,
+            not_for_replication: fk.is_not_for_replication,
+            with_no_check: fk.with_no_check,
+        }
+    }
+}
+
+impl From<&SqlUniqueConstraint> for simple::SimpleUniqueConstraint {
+    fn from(uq: &SqlUniqueConstraint) -> Self {
+        simple::SimpleUniqueConstraint {
+            name: uq.name.clone(),
+            columns: uq
+                .column_specifications
+                .iter()
+                .map(simple::SimpleIndexColumn::from)
+                .collect(),
+            clustered: uq.is_clustered,
+            filegroup: uq.filegroup.as_ref().map(|n| n.name.clone()),
+        }
+    }
+}
+
+impl From<&SqlCheckConstraint> for simple::SimpleCheckConstraint {
+    fn from(ck: &SqlCheckConstraint) -> Self {
+        simple::SimpleCheckConstraint {
+            name: ck.name.clone(),
+            expression: ck.expression.clone(),
+            not_for_replication: ck.is_not_for_replication,
+            checked: ck.is_checked,
+        }
+    }
+}
+
+impl From<&SqlDefaultConstraint> for simple::SimpleDefault {
+    fn from(df: &SqlDefaultConstraint) -> Self {
+        simple::SimpleDefault {
+            name: df.name.clone(),
+            expression: df.expression.clone(),
+        }
+    }
+}
+
+impl From<&SqlIndex> for simple::SimpleIndex {
+    fn from(ix: &SqlIndex) -> Self {
+        simple::SimpleIndex {
+            name: ix.name.leaf().to_string(),
+            kind: simple::SimpleIndexKind::RowStore,
+            columns: ix
+                .column_specifications
+                .iter()
+                .map(simple::SimpleIndexColumn::from)
+                .collect(),
+            included_columns: ix
+                .included_columns
+                .iter()
+                .map(|c| c.leaf().to_string())
+                .collect(),
+            filter: ix.filter_predicate.clone(),
+            unique: ix.is_unique,
+            clustered: ix.is_clustered,
+            fill_factor: ix.fill_factor,
+            disabled: ix.is_disabled,
+            data_compression: ix
+                .data_compression
+                .iter()
+                .map(simple::SimpleDataCompression::from)
+                .collect(),
+            filegroup: ix.filegroup.as_ref().map(|n| n.name.clone()),
+        }
+    }
+}
+
+impl From<&SqlColumnStoreIndex> for simple::SimpleIndex {
+    fn from(ix: &SqlColumnStoreIndex) -> Self {
+        simple::SimpleIndex {
+            name: ix.name.leaf().to_string(),
+            kind: simple::SimpleIndexKind::ColumnStore,
+            columns: ix
+                .columns
+                .iter()
+                .map(|c| simple::SimpleIndexColumn {
+                    name: c.leaf().to_string(),
+                    descending: false,
+                })
+                .collect(),
+            included_columns: Vec::new(),
+            filter: ix.filter_predicate.clone(),
+            unique: false,
+            clustered: ix.is_clustered,
+            fill_factor: None,
+            disabled: ix.is_disabled,
+            data_compression: ix
+                .data_compression
+                .iter()
+                .map(simple::SimpleDataCompression::from)
+                .collect(),
+            filegroup: ix.filegroup.as_ref().map(|n| n.name.clone()),
+        }
+    }
+}
+
+impl From<&SqlXmlIndex> for simple::SimpleIndex {
+    fn from(ix: &SqlXmlIndex) -> Self {
+        simple::SimpleIndex {
+            name: ix.name.leaf().to_string(),
+            kind: simple::SimpleIndexKind::Xml {
+                primary: ix.is_primary,
+                secondary_type: ix.secondary_type,
+            },
+            columns: vec![simple::SimpleIndexColumn {
+                name: ix.column.leaf().to_string(),
+                descending: false,
+            }],
+            included_columns: Vec::new(),
+            filter: None,
+            unique: false,
+            clustered: false,
+            fill_factor: None,
+            disabled: false,
+            data_compression: Vec::new(),
+            filegroup: None,
+        }
+    }
+}
+
+impl From<&SqlSpatialIndex> for simple::SimpleIndex {
+    fn from(ix: &SqlSpatialIndex) -> Self {
+        simple::SimpleIndex {
+            name: ix.name.leaf().to_string(),
+            kind: simple::SimpleIndexKind::Spatial,
+            columns: vec![simple::SimpleIndexColumn {
+                name: ix.column.leaf().to_string(),
+                descending: false,
+            }],
+            included_columns: Vec::new(),
+            filter: None,
+            unique: false,
+            clustered: false,
+            fill_factor: None,
+            disabled: false,
+            data_compression: ix
+                .data_compression
+                .iter()
+                .map(simple::SimpleDataCompression::from)
+                .collect(),
+            filegroup: ix.filegroup.as_ref().map(|n| n.name.clone()),
+        }
+    }
+}
+
+impl From<&SqlFullTextCatalog> for simple::SimpleFullTextCatalog {
+    fn from(c: &SqlFullTextCatalog) -> Self {
+        simple::SimpleFullTextCatalog {
+            name: c.name.name.clone(),
+            accent_sensitive: c.is_accent_sensitive,
+            default: c.is_default,
+            owner: c.authorizer.as_ref().map(|n| n.name.clone()),
+        }
+    }
+}
+
+impl From<&SqlFullTextStopList> for simple::SimpleFullTextStopList {
+    fn from(sl: &SqlFullTextStopList) -> Self {
+        simple::SimpleFullTextStopList {
+            name: sl.name.name.clone(),
+            owner: sl.authorizer.as_ref().map(|n| n.name.clone()),
+        }
+    }
+}
+
+impl From<&SqlFullTextIndex> for simple::SimpleFullTextIndex {
+    fn from(ix: &SqlFullTextIndex) -> Self {
+        let stop_list = if ix.is_stop_list_off {
+            simple::SimpleStopList::Off
+        } else {
+            match &ix.stop_list {
+                Some(name) => simple::SimpleStopList::Named(name.name.clone()),
+                None => simple::SimpleStopList::System,
+            }
+        };
+
+        simple::SimpleFullTextIndex {
+            columns: ix
+                .columns
+                .iter()
+                .map(|c| simple::SimpleFullTextColumn {
+                    name: c.column.leaf().to_string(),
+                    language: c.language_id,
+                    type_column: c.type_column.as_ref().map(|t| t.leaf().to_string()),
+                    statistical_semantics: c.is_statistical_semantics,
+                })
+                .collect(),
+            key_index: ix.key_index.leaf().to_string(),
+            catalog: ix.catalog.as_ref().map(|n| n.name.clone()),
+            filegroup: ix.filegroup.as_ref().map(|n| n.name.clone()),
+            stop_list,
+            change_tracking: ix.change_tracking,
+        }
+    }
+}
+
+impl From<&index::SqlDataCompressionOption> for simple::SimpleDataCompression {
+    fn from(dc: &index::SqlDataCompressionOption) -> Self {
+        simple::SimpleDataCompression {
+            level: dc.level,
+            partition: dc.partition_number,
+        }
+    }
+}
+
+impl From<&constraint::SqlIndexedColumnSpecification> for simple::SimpleIndexColumn {
+    fn from(spec: &constraint::SqlIndexedColumnSpecification) -> Self {
+        simple::SimpleIndexColumn {
+            name: spec.column.leaf().to_string(),
+            descending: !spec.is_ascending,
         }
     }
 }
 
 impl From<&table::SqlSimpleColumnTableElement> for simple::SimpleTableColumn {
     fn from(st: &table::SqlSimpleColumnTableElement) -> Self {
-        let mut nullable = false;
-        st.properties.iter().flatten().for_each(|property| {
-            if property.name.as_str() == "IsNullable" {
-                nullable = property.value.as_ref().unwrap_or(&"".to_string()) != "False"
+        let identity = match &st.identity_specifier {
+            Some(is) => Some(simple::SimpleIdentity {
+                seed: is.seed,
+                increment: is.increment,
+                not_for_replication: is.is_not_for_replication,
+            }),
+            None if st.property("IsIdentity").as_deref() == Some("True") => {
+                Some(simple::SimpleIdentity {
+                    seed: 1,
+                    increment: 1,
+                    not_for_replication: false,
+                })
             }
-        });
+            None => None,
+        };
 
-        let rname = simple::remove_delimiters(&st.name);
-        let row_name_last = rname.split('.').last().unwrap();
+        simple::SimpleTableColumn {
+            name: st.name.leaf().to_string(),
+            // DacFx only writes `IsNullable` for NOT NULL columns
+            nullable: st.property("IsNullable").as_deref() != Some("False"),
+            collation: st.property("Collation"),
+            ty: Some(simple::SimpleColumnType::from(&st.type_specifier)),
+            user_type: None,
+            default: None,
+            identity,
+            computed: None,
+        }
+    }
+}
 
+impl From<&table::SqlComputedColumnTableElement> for simple::SimpleTableColumn {
+    fn from(st: &table::SqlComputedColumnTableElement) -> Self {
         simple::SimpleTableColumn {
-            name: row_name_last.to_string(),
-            nullable,
-            ty: simple::SimpleColumnType::from(&st.relationship.entry.element_type_specifier),
+            name: st.name.leaf().to_string(),
+            nullable: st.property("IsNullable").as_deref() != Some("False"),
+            collation: st.property("Collation"),
+            ty: st
+                .type_specifier
+                .as_ref()
+                .map(simple::SimpleColumnType::from),
+            user_type: None,
             default: None,
+            identity: None,
+            computed: Some(simple::SimpleComputedColumn {
+                expression: st.expression.clone(),
+                persisted: st.is_persisted,
+                dependencies: st.dependencies.clone(),
+            }),
         }
     }
 }
 
 impl From<&table::ElementTypeSpecifier> for simple::SimpleColumnType {
     fn from(st: &table::ElementTypeSpecifier) -> Self {
-        match st.type_specifier_rela.entry.element.name.as_str() {
-            "[int]" => simple::SimpleColumnType::Int,
-            "[bigint]" => simple::SimpleColumnType::BigInt,
-            "[nvarchar]" => match &st.property {
-                Some(prop) => match prop.name.as_str() {
-                    "Length" => {
-                        let n = prop.get_value().parse::<i32>().unwrap();
-                        simple::SimpleColumnType::Nvarchar(n)
-                    }
-                    _ => simple::SimpleColumnType::Nvarchar(0),
-                },
-                None => simple::SimpleColumnType::Nvarchar(0),
-            },
-            "[varchar]" => match &st.property {
-                Some(prop) => match prop.name.as_str() {
-                    "Length" => {
-                        let n = prop.get_value().parse::<i32>().unwrap();
-                        simple::SimpleColumnType::Varchar(n)
-                    }
-                    _ => simple::SimpleColumnType::Varchar(0),
-                },
-                None => simple::SimpleColumnType::Varchar(0),
-            },
-            "[datetime2]" => match &st.property {
-                Some(prop) => match prop.name.as_str() {
-                    "Scale" => {
-                        let n = prop.get_value().parse::<i8>().unwrap();
-                        simple::SimpleColumnType::DateTime2(n)
-                    }
-                    _ => simple::SimpleColumnType::DateTime2(0),
-                },
-                None => simple::SimpleColumnType::DateTime2(0),
-            },
-            _ => panic!("Unknown type: {:#?}", st),
+        use simple::SimpleColumnType as T;
+
+        match st.type_reference.name.as_str() {
+            "[bit]" => T::Bit,
+            "[tinyint]" => T::TinyInt,
+            "[smallint]" => T::SmallInt,
+            "[int]" => T::Int,
+            "[bigint]" => T::BigInt,
+            "[decimal]" => T::Decimal(st.facet("Precision", 18), st.facet("Scale", 0)),
+            "[numeric]" => T::Numeric(st.facet("Precision", 18), st.facet("Scale", 0)),
+            "[money]" => T::Money,
+            "[smallmoney]" => T::SmallMoney,
+            "[float]" => T::Float(st.facet("Precision", 53)),
+            "[real]" => T::Real,
+            "[date]" => T::Date,
+            "[time]" => T::Time(st.facet("Scale", 7)),
+            "[datetime]" => T::DateTime,
+            "[smalldatetime]" => T::SmallDateTime,
+            "[datetime2]" => T::DateTime2(st.facet("Scale", 7)),
+            "[datetimeoffset]" => T::DateTimeOffset(st.facet("Scale", 7)),
+            "[char]" => T::Char(st.into()),
+            "[varchar]" => T::Varchar(st.into()),
+            "[text]" => T::Text,
+            "[nchar]" => T::Nchar(st.into()),
+            "[nvarchar]" => T::Nvarchar(st.into()),
+            "[sysname]" => T::Nvarchar(simple::SimpleColumnLength::Bounded(128)),
+            "[ntext]" => T::Ntext,
+            "[binary]" => T::Binary(st.into()),
+            "[varbinary]" => T::Varbinary(st.into()),
+            "[image]" => T::Image,
+            "[uniqueidentifier]" => T::UniqueIdentifier,
+            "[xml]" => T::Xml,
+            "[sql_variant]" => T::SqlVariant,
+            "[hierarchyid]" => T::HierarchyId,
+            "[geography]" => T::Geography,
+            "[geometry]" => T::Geometry,
+            "[rowversion]" | "[timestamp]" => T::RowVersion,
+            _ => T::Other(st.type_name.clone()),
+        }
+    }
+}
+
+impl From<&table::ElementTypeSpecifier> for simple::SimpleColumnLength {
+    fn from(st: &table::ElementTypeSpecifier) -> Self {
+        if st.property("IsMax").as_deref() == Some("True") {
+            return simple::SimpleColumnLength::Max;
+        }
+        match st.property("Length") {
+            Some(_) => simple::SimpleColumnLength::Bounded(st.facet("Length", 1)),
+            None => simple::SimpleColumnLength::Unspecified,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simple::{SimpleColumnLength as L, SimpleColumnType as T};
+
+    fn column(name: &str, ty: Option<T>) -> simple::SimpleTableColumn {
+        simple::SimpleTableColumn {
+            name: name.to_string(),
+            ty,
+            user_type: None,
+            nullable: true,
+            collation: None,
+            default: None,
+            identity: None,
+            computed: None,
         }
     }
+
+    #[test]
+    fn elements_failing_to_convert_are_kept_as_unknown() {
+        let xml = r#"<DataSchemaModel><Model>
+            <Element Type="SqlFullTextIndex" Name="[dbo].[T]" />
+            <Element Type="SqlSchema" Name="[s]" />
+        </Model></DataSchemaModel>"#;
+        let model = from_xml(xml).unwrap();
+
+        assert!(matches!(
+            &model.model.element[0],
+            ElementEnum::Unknown { ty, error: Some(_), .. } if ty == "SqlFullTextIndex"
+        ));
+        assert!(matches!(&model.model.element[1], ElementEnum::SqlSchema(_)));
+
+        let simple = simple::SimpleDacPacModel::from(&model);
+        assert_eq!(simple.unconverted.len(), 1);
+        assert_eq!(simple.unconverted[0].ty, "SqlFullTextIndex");
+        assert_eq!(simple.unconverted[0].name.as_deref(), Some("[dbo].[T]"));
+        assert!(!simple.unconverted[0].error.is_empty());
+        assert!(from_xml("<DataSchemaModel><Model>").is_err());
+    }
+
+    #[test]
+    fn alias_types_resolve_to_their_base_type() {
+        let column = |name: &str| {
+            format!(
+                r#"<Entry><Element Type="SqlSimpleColumn" Name="{name}">
+                <Relationship Name="TypeSpecifier"><Entry><Element Type="SqlTypeSpecifier">
+                  <Relationship Name="Type"><Entry><References Name="[dbo].[Phone]" /></Entry></Relationship>
+                </Element></Entry></Relationship>
+                </Element></Entry>"#
+            )
+        };
+        let schema = r#"<Relationship Name="Schema"><Entry><References ExternalSource="BuiltIns" Name="[dbo]" /></Entry></Relationship>"#;
+        let xml = format!(
+            r#"<DataSchemaModel><Model>
+            <Element Type="SqlUserDefinedDataType" Name="[dbo].[Phone]">
+              <Property Name="Length" Value="20" />
+              <Relationship Name="Type"><Entry><References ExternalSource="BuiltIns" Name="[varchar]" /></Entry></Relationship>
+              {schema}
+            </Element>
+            <Element Type="SqlTable" Name="[dbo].[T]">
+              <Relationship Name="Columns">{}</Relationship>
+              {schema}
+            </Element>
+            <Element Type="SqlTableType" Name="[dbo].[TT]">
+              <Relationship Name="Columns">{}</Relationship>
+              {schema}
+            </Element>
+            <Element Type="SqlProcedure" Name="[dbo].[P]">
+              <Property Name="BodyScript"><Value><![CDATA[RETURN]]></Value></Property>
+              <Relationship Name="Parameters"><Entry><Element Type="SqlSubroutineParameter" Name="[dbo].[P].[@phone]">
+                <Relationship Name="Type"><Entry><Element Type="SqlTypeSpecifier">
+                  <Relationship Name="Type"><Entry><References Name="[dbo].[Phone]" /></Entry></Relationship>
+                </Element></Entry></Relationship>
+              </Element></Entry></Relationship>
+              {schema}
+            </Element>
+            </Model></DataSchemaModel>"#,
+            column("[dbo].[T].[Phone]"),
+            column("[dbo].[TT].[Phone]"),
+        );
+        let model = simple::SimpleDacPacModel::from(&from_xml(&xml).unwrap());
+        let phone: ObjectName = "[dbo].[Phone]".parse().unwrap();
+        let varchar = T::Varchar(L::Bounded(20));
+
+        for table in [&model.tables[0], &model.table_types[0]] {
+            assert_eq!(table.columns[0].ty, Some(varchar.clone()));
+            assert_eq!(table.columns[0].user_type, Some(phone.clone()));
+        }
+        let parameter = &model.procedures[0].parameters[0];
+        assert_eq!(parameter.ty, varchar);
+        assert_eq!(parameter.type_name, phone);
+    }
+
+    #[test]
+    fn constraints_and_indexes_land_on_their_table() {
+        let int = r#"<Relationship Name="TypeSpecifier"><Entry><Element Type="SqlTypeSpecifier">
+              <Relationship Name="Type"><Entry><References ExternalSource="BuiltIns" Name="[int]" /></Entry></Relationship>
+            </Element></Entry></Relationship>"#;
+        let schema = r#"<Relationship Name="Schema"><Entry><References ExternalSource="BuiltIns" Name="[dbo]" /></Entry></Relationship>"#;
+        let reference = |relationship: &str, name: &str| {
+            format!(
+                r#"<Relationship Name="{relationship}"><Entry><References Name="{name}" /></Entry></Relationship>"#
+            )
+        };
+        let column_specification = |name: &str| {
+            format!(
+                r#"<Relationship Name="ColumnSpecifications"><Entry><Element Type="SqlIndexedColumnSpecification">{}</Element></Entry></Relationship>"#,
+                reference("Column", name)
+            )
+        };
+        let xml = format!(
+            r#"<DataSchemaModel><Model>
+            <Element Type="SqlTable" Name="[dbo].[P]">
+              <Relationship Name="Columns"><Entry><Element Type="SqlSimpleColumn" Name="[dbo].[P].[Id]">{int}</Element></Entry></Relationship>
+              {schema}
+            </Element>
+            <Element Type="SqlTable" Name="[dbo].[T]">
+              <Relationship Name="Columns">
+                <Entry><Element Type="SqlSimpleColumn" Name="[dbo].[T].[Id]">
+                  {int}
+                  <Relationship Name="IdentitySpecifier"><Entry><Element Type="SqlIdentitySpecifier">
+                    <Property Name="IdentitySeed" Value="10" />
+                    <Property Name="IdentityIncrement" Value="5" />
+                  </Element></Entry></Relationship>
+                </Element></Entry>
+                <Entry><Element Type="SqlSimpleColumn" Name="[dbo].[T].[ParentId]">{int}</Element></Entry>
+              </Relationship>
+              {schema}
+            </Element>
+            <Element Type="SqlPrimaryKeyConstraint" Name="[dbo].[PK_T]">
+              {pk_column}{t}
+            </Element>
+            <Element Type="SqlPrimaryKeyConstraint" Name="[dbo].[PK_P]">
+              {p_column}{p}
+            </Element>
+            <Element Type="SqlForeignKeyConstraint" Name="[dbo].[FK_T_P]">
+              {parent}{t}{foreign_column}{p_table}
+            </Element>
+            <Element Type="SqlUniqueConstraint" Name="[dbo].[UQ_T]">
+              {uq_column}{t}
+            </Element>
+            <Element Type="SqlCheckConstraint" Name="[dbo].[CK_T]">
+              <Property Name="CheckExpressionScript"><Value><![CDATA[[ParentId] > 0]]></Value></Property>
+              {t}
+            </Element>
+            <Element Type="SqlDefaultConstraint">
+              <Property Name="DefaultExpressionScript"><Value><![CDATA[((0))]]></Value></Property>
+              {t}{for_column}
+            </Element>
+            <Element Type="SqlDefaultConstraint">
+              <Property Name="DefaultExpressionScript"><Value><![CDATA[((0))]]></Value></Property>
+              {t}{missing_column}
+            </Element>
+            <Element Type="SqlIndex" Name="[dbo].[T].[IX_T_ParentId]">
+              {uq_column}{t_indexed}
+            </Element>
+            <Element Type="SqlIndex" Name="[dbo].[Missing].[IX]">
+              {uq_column}{missing_indexed}
+            </Element>
+            </Model></DataSchemaModel>"#,
+            pk_column = column_specification("[dbo].[T].[Id]"),
+            p_column = column_specification("[dbo].[P].[Id]"),
+            uq_column = column_specification("[dbo].[T].[ParentId]"),
+            t = reference("DefiningTable", "[dbo].[T]"),
+            p = reference("DefiningTable", "[dbo].[P]"),
+            parent = reference("Columns", "[dbo].[T].[ParentId]"),
+            foreign_column = reference("ForeignColumns", "[dbo].[P].[Id]"),
+            p_table = reference("ForeignTable", "[dbo].[P]"),
+            for_column = reference("ForColumn", "[dbo].[T].[ParentId]"),
+            missing_column = reference("ForColumn", "[dbo].[T].[Missing]"),
+            t_indexed = reference("IndexedObject", "[dbo].[T]"),
+            missing_indexed = reference("IndexedObject", "[dbo].[Missing]"),
+        );
+        let model = simple::SimpleDacPacModel::from(&from_xml(&xml).unwrap());
+        assert!(model.unconverted.is_empty());
+        let [p, t] = &model.tables[..] else {
+            panic!("Expected two tables")
+        };
+
+        let pk = t.primary_key.as_ref().unwrap();
+        assert_eq!(pk.name, Some(ObjectName::new("dbo", "PK_T")));
+        assert_eq!(pk.columns[0].name, "Id");
+        assert_eq!(p.primary_key.as_ref().unwrap().columns[0].name, "Id");
+
+        assert_eq!(t.foreign_keys.len(), 1);
+        assert_eq!(t.foreign_keys[0].columns, ["ParentId"]);
+        assert_eq!(t.foreign_keys[0].foreign_table, ObjectName::new("dbo", "P"));
+        assert_eq!(t.foreign_keys[0].foreign_columns, ["Id"]);
+        assert!(p.foreign_keys.is_empty());
+
+        assert_eq!(t.unique_constraints[0].columns[0].name, "ParentId");
+        assert_eq!(t.check_constraints[0].expression, "[ParentId] > 0");
+
+        let [id, parent_id] = &t.columns[..] else {
+            panic!("Expected two columns")
+        };
+        let identity = id.identity.as_ref().unwrap();
+        assert_eq!((identity.seed, identity.increment), (10, 5));
+        assert!(id.default.is_none());
+        assert!(parent_id.identity.is_none());
+        assert_eq!(parent_id.default.as_ref().unwrap().expression, "((0))");
+
+        assert_eq!(t.indexes.len(), 1);
+        assert_eq!(t.indexes[0].name, "IX_T_ParentId");
+        assert!(p.indexes.is_empty());
+    }
+
+    #[test]
+    fn partitioned_table() {
+        let xml = r#"<DataSchemaModel><Model>
+            <Element Type="SqlPartitionFunction" Name="[pf]">
+              <Property Name="Range" Value="2" />
+              <Relationship Name="BoundaryValues">
+                <Entry><Element Type="SqlPartitionValue"><Property Name="ExpressionScript"><Value><![CDATA[100]]></Value></Property></Element></Entry>
+              </Relationship>
+              <Relationship Name="ParameterType"><Entry><Element Type="SqlTypeSpecifier">
+                <Relationship Name="Type"><Entry><References ExternalSource="BuiltIns" Name="[int]" /></Entry></Relationship>
+              </Element></Entry></Relationship>
+            </Element>
+            <Element Type="SqlPartitionScheme" Name="[ps]">
+              <Relationship Name="FilegroupSpecifiers">
+                <Entry><Element Type="SqlFilegroupSpecifier"><Relationship Name="Filegroup"><Entry><References ExternalSource="BuiltIns" Name="[PRIMARY]" /></Entry></Relationship></Element></Entry>
+                <Entry><Element Type="SqlFilegroupSpecifier"><Relationship Name="Filegroup"><Entry><References Name="[FG2]" /></Entry></Relationship></Element></Entry>
+              </Relationship>
+              <Relationship Name="PartitionFunction"><Entry><References Name="[pf]" /></Entry></Relationship>
+            </Element>
+            <Element Type="SqlTable" Name="[dbo].[T]">
+              <Relationship Name="Columns"><Entry><Element Type="SqlSimpleColumn" Name="[dbo].[T].[Id]">
+                <Relationship Name="TypeSpecifier"><Entry><Element Type="SqlTypeSpecifier">
+                  <Relationship Name="Type"><Entry><References ExternalSource="BuiltIns" Name="[int]" /></Entry></Relationship>
+                </Element></Entry></Relationship>
+              </Element></Entry></Relationship>
+              <Relationship Name="PartitionColumn"><Entry><References Name="[dbo].[T].[Id]" /></Entry></Relationship>
+              <Relationship Name="PartitionScheme"><Entry><References Name="[ps]" /></Entry></Relationship>
+              <Relationship Name="Schema"><Entry><References ExternalSource="BuiltIns" Name="[dbo]" /></Entry></Relationship>
+            </Element>
+            </Model></DataSchemaModel>"#;
+        let model = simple::SimpleDacPacModel::from(&from_xml(xml).unwrap());
+
+        let function = &model.partition_functions[0];
+        assert_eq!(function.parameter_type, T::Int);
+        assert_eq!(function.range, storage::PartitionRange::Right);
+        assert_eq!(function.boundaries, ["100"]);
+        let scheme = &model.partition_schemes[0];
+        assert_eq!(scheme.function, "pf");
+        assert_eq!(scheme.filegroups, ["PRIMARY", "FG2"]);
+        assert!(matches!(
+            &model.tables[0].storage,
+            simple::SimpleStorage::PartitionScheme { scheme, column } if scheme == "ps" && column == "Id"
+        ));
+    }
+
+    #[test]
+    fn computed_column_types() {
+        let columns = [column("Price", Some(T::Money)), column("Qty", Some(T::Int))];
+        let ty = |expression| computed_column_type(expression, &columns);
+
+        assert_eq!(ty("([Price]*[Qty])"), None);
+        assert_eq!(ty("([Price])"), Some(T::Money));
+        assert_eq!(
+            ty("CAST([Price] * [Qty] AS decimal(10, 2))"),
+            Some(T::Decimal(10, 2))
+        );
+        assert_eq!(
+            ty("(CONVERT([nvarchar](max),[Qty]))"),
+            Some(T::Nvarchar(L::Max))
+        );
+        assert_eq!(
+            ty("(try_cast(CAST([Qty] AS int) AS [varchar](20)))"),
+            Some(T::Varchar(L::Bounded(20)))
+        );
+        assert_eq!(
+            ty("CAST([Qty] AS varchar)"),
+            Some(T::Varchar(L::Bounded(30)))
+        );
+        assert_eq!(ty("(CAST([Qty] AS int) + 1)"), None);
+        assert_eq!(ty("(CONVERT(int, ')') + 1)"), None);
+    }
 }
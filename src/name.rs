@@ -0,0 +1,182 @@
+//! Multipart object names such as `[dbo].[Table].[Column]`.
+//!
+//! DacFx writes the name of every element and reference with each part
+//! quoted in brackets, a `]` within a part being escaped as `]]`. Names are
+//! parsed when the elements are read, so the rest of the crate never has to
+//! deal with the delimiters.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Error, Result};
+
+/// The name of a schema-scoped object, or of one of its sub-objects such as
+/// a column, an index or a parameter.
+///
+/// Objects outside of schemas (schemas themselves, principals, filegroups...)
+/// only have a `name`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectName {
+    /// The database of an object referenced in another database
+    pub database: Option<String>,
+    pub schema: Option<String>,
+    pub name: String,
+    /// The column, index, parameter... of the object
+    pub sub: Option<String>,
+}
+
+impl ObjectName {
+    /// The name of the object `schema.name`
+    pub fn new(schema: impl Into<String>, name: impl Into<String>) -> Self {
+        ObjectName {
+            database: None,
+            schema: Some(schema.into()),
+            name: name.into(),
+            sub: None,
+        }
+    }
+
+    /// Returns the name of the object a sub-object belongs to, e.g. the table
+    /// of a column
+    pub fn parent(&self) -> ObjectName {
+        ObjectName {
+            database: self.database.clone(),
+            schema: self.schema.clone(),
+            name: self.name.clone(),
+            sub: None,
+        }
+    }
+
+    /// Returns the last part of the name, e.g. the column name of a column
+    pub fn leaf(&self) -> &str {
+        self.sub.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Splits a multipart identifier such as `[db].[dbo].[Table]` into its
+/// unquoted parts. Parts may also be written without brackets, in which case
+/// they can't contain dots.
+pub fn parse_multipart(s: &str) -> Result<Vec<String>> {
+    if s.is_empty() {
+        bail!("Empty name");
+    }
+
+    let mut parts = Vec::new();
+    let mut chars = s.chars().peekable();
+    loop {
+        let mut part = String::new();
+        if chars.next_if_eq(&'[').is_some() {
+            loop {
+                match chars.next() {
+                    Some(']') if chars.next_if_eq(&']').is_some() => part.push(']'),
+                    Some(']') => break,
+                    Some(c) => part.push(c),
+                    None => bail!("Missing `]` in name `{s}`"),
+                }
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != '.') {
+                if c == '[' || c == ']' {
+                    bail!("Unexpected `{c}` in name `{s}`");
+                }
+                part.push(c);
+            }
+        }
+        parts.push(part);
+
+        match chars.next() {
+            None => return Ok(parts),
+            Some('.') => {}
+            Some(c) => bail!("Unexpected `{c}` after `]` in name `{s}`"),
+        }
+    }
+}
+
+/// Parses `name`, `schema.name`, `schema.name.sub` and
+/// `database.schema.name.sub` names.
+///
+/// Three part names are `schema.name.sub`, unless the first part is a SQLCMD
+/// variable such as `$(OtherDb)`, the way DacFx writes references to objects
+/// of another database: a three part name with a literal database name can't
+/// be told apart from the name of a column.
+impl FromStr for ObjectName {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = parse_multipart(s)?;
+        let database = match parts.len() {
+            1 | 2 => None,
+            3 if parts[0].starts_with("$(") => Some(parts.remove(0)),
+            3 => None,
+            4 => Some(parts.remove(0)),
+            _ => return Err(anyhow!("Name `{s}` has more than four parts")),
+        };
+        let mut parts = parts.into_iter();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(name), None, None) => Ok(ObjectName {
+                database,
+                schema: None,
+                name,
+                sub: None,
+            }),
+            (Some(schema), Some(name), sub) => Ok(ObjectName {
+                database,
+                schema: Some(schema),
+                name,
+                sub,
+            }),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Writes the name quoted the way DacFx does, e.g. `[dbo].[Table]`
+impl fmt::Display for ObjectName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts = self
+            .database
+            .iter()
+            .chain(self.schema.iter())
+            .chain(Some(&self.name))
+            .chain(self.sub.iter());
+        for (i, part) in parts.enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "[{}]", part.replace(']', "]]"))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> ObjectName {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parts() {
+        assert_eq!(parse("[dbo].[T]"), ObjectName::new("dbo", "T"));
+        assert_eq!(parse("[a]]b].[c.d]").name, "c.d");
+        assert_eq!(parse("[a]]b].[c.d]").schema.as_deref(), Some("a]b"));
+        assert_eq!(parse("[dbo].[T].[c]").sub.as_deref(), Some("c"));
+        assert_eq!(parse("[PRIMARY]").schema, None);
+        assert!("[dbo].[T".parse::<ObjectName>().is_err());
+        assert!("[a].[b].[c].[d].[e]".parse::<ObjectName>().is_err());
+    }
+
+    #[test]
+    fn other_database() {
+        let table = parse("[$(OtherDb)].[dbo].[T]");
+        assert_eq!(table.database.as_deref(), Some("$(OtherDb)"));
+        assert_eq!(table.parent(), table);
+
+        let column = parse("[$(OtherDb)].[dbo].[T].[c]");
+        assert_eq!(column.parent(), table);
+        assert_eq!(column.leaf(), "c");
+        assert_eq!(column.to_string(), "[$(OtherDb)].[dbo].[T].[c]");
+    }
+}
@@ -0,0 +1,255 @@
+//! Procedures, functions and triggers.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::element::RawElement;
+use crate::name::ObjectName;
+use crate::table::ElementTypeSpecifier;
+
+#[derive(Debug, PartialEq)]
+pub struct SqlProcedure {
+    pub name: ObjectName,
+    pub schema: String,
+    pub parameters: Vec<SqlSubroutineParameter>,
+    /// The statements after `AS`, as written in the source
+    pub body_script: String,
+    pub execute_as: Option<ExecuteAs>,
+    /// Names of the objects used by the body
+    pub body_dependencies: Vec<ObjectName>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlScalarFunction {
+    pub name: ObjectName,
+    pub schema: String,
+    pub parameters: Vec<SqlSubroutineParameter>,
+    pub return_type: ElementTypeSpecifier,
+    pub body_script: String,
+    pub execute_as: Option<ExecuteAs>,
+    pub body_dependencies: Vec<ObjectName>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlInlineTableValuedFunction {
+    pub name: ObjectName,
+    pub schema: String,
+    pub parameters: Vec<SqlSubroutineParameter>,
+    /// The returned `SELECT` statement
+    pub body_script: String,
+    pub body_dependencies: Vec<ObjectName>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlMultiStatementTableValuedFunction {
+    pub name: ObjectName,
+    pub schema: String,
+    pub parameters: Vec<SqlSubroutineParameter>,
+    pub body_script: String,
+    pub execute_as: Option<ExecuteAs>,
+    pub body_dependencies: Vec<ObjectName>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlDmlTrigger {
+    pub name: ObjectName,
+    /// The table or view the trigger is defined on
+    pub parent: ObjectName,
+    pub timing: TriggerTiming,
+    pub is_insert_trigger: bool,
+    pub is_update_trigger: bool,
+    pub is_delete_trigger: bool,
+    pub is_not_for_replication: bool,
+    pub body_script: String,
+    pub execute_as: Option<ExecuteAs>,
+    pub body_dependencies: Vec<ObjectName>,
+}
+
+/// A parameter of a procedure or a function
+#[derive(Debug, PartialEq)]
+pub struct SqlSubroutineParameter {
+    pub name: ObjectName,
+    pub type_specifier: ElementTypeSpecifier,
+    pub direction: ParameterDirection,
+    /// The default value, as written in the source
+    pub default_expression: Option<String>,
+    /// `READONLY` table-valued parameters
+    pub is_read_only: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParameterDirection {
+    #[default]
+    Input,
+    /// `OUTPUT` parameters, which are also inputs
+    Output,
+}
+
+/// The `EXECUTE AS` clause of a module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecuteAs {
+    Caller,
+    Owner,
+    SelfUser,
+    User(ObjectName),
+}
+
+/// `AFTER` (or `FOR`) and `INSTEAD OF` triggers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TriggerTiming {
+    #[default]
+    After,
+    InsteadOf,
+}
+
+impl TryFrom<&RawElement> for SqlProcedure {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlProcedure {
+            schema: e.schema()?,
+            parameters: parameters(e)?,
+            body_script: body_script(e)?,
+            execute_as: execute_as(e)?,
+            body_dependencies: e.object_references("BodyDependencies")?,
+            name: e.object_name("Procedure")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlScalarFunction {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        let return_type = e
+            .elements("Type")
+            .first()
+            .map(|ts| ElementTypeSpecifier::try_from(*ts))
+            .transpose()?
+            .ok_or_else(|| anyhow!("{} has no return Type", e.display_name()))?;
+
+        Ok(SqlScalarFunction {
+            schema: e.schema()?,
+            parameters: parameters(e)?,
+            return_type,
+            body_script: body_script(e)?,
+            execute_as: execute_as(e)?,
+            body_dependencies: e.object_references("BodyDependencies")?,
+            name: e.object_name("Function")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlInlineTableValuedFunction {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlInlineTableValuedFunction {
+            schema: e.schema()?,
+            parameters: parameters(e)?,
+            body_script: body_script(e)?,
+            body_dependencies: e.object_references("BodyDependencies")?,
+            name: e.object_name("Function")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlMultiStatementTableValuedFunction {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlMultiStatementTableValuedFunction {
+            schema: e.schema()?,
+            parameters: parameters(e)?,
+            body_script: body_script(e)?,
+            execute_as: execute_as(e)?,
+            body_dependencies: e.object_references("BodyDependencies")?,
+            name: e.object_name("Function")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlDmlTrigger {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlDmlTrigger {
+            parent: e.required_object_reference("Parent")?,
+            timing: e.parse_property("SqlTriggerType")?.unwrap_or_default(),
+            is_insert_trigger: e.bool_property("IsInsertTrigger", false),
+            is_update_trigger: e.bool_property("IsUpdateTrigger", false),
+            is_delete_trigger: e.bool_property("IsDeleteTrigger", false),
+            is_not_for_replication: e.bool_property("IsNotForReplication", false),
+            body_script: body_script(e)?,
+            execute_as: execute_as(e)?,
+            body_dependencies: e.object_references("BodyDependencies")?,
+            name: e.object_name("Trigger")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlSubroutineParameter {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        let name = e.object_name("Parameter")?;
+        let type_specifier = e
+            .elements("Type")
+            .first()
+            .map(|ts| ElementTypeSpecifier::try_from(*ts))
+            .transpose()?
+            .ok_or_else(|| anyhow!("Parameter {name} has no Type"))?;
+
+        Ok(SqlSubroutineParameter {
+            type_specifier,
+            direction: if e.bool_property("IsOutput", false) {
+                ParameterDirection::Output
+            } else {
+                ParameterDirection::Input
+            },
+            default_expression: e.property_value("DefaultExpressionScript"),
+            is_read_only: e.bool_property("IsReadOnly", false),
+            name,
+        })
+    }
+}
+
+/// `FOR` is a synonym of `AFTER`
+impl FromStr for TriggerTiming {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "0" | "1" | "For" | "After" => Ok(TriggerTiming::After),
+            "2" | "InsteadOf" => Ok(TriggerTiming::InsteadOf),
+            _ => Err(anyhow!("Unknown trigger type `{s}`")),
+        }
+    }
+}
+
+/// Reads the `Parameters` relationship of a procedure or a function
+fn parameters(e: &RawElement) -> Result<Vec<SqlSubroutineParameter>> {
+    e.elements("Parameters")
+        .into_iter()
+        .map(SqlSubroutineParameter::try_from)
+        .collect()
+}
+
+fn body_script(e: &RawElement) -> Result<String> {
+    e.property_value("BodyScript")
+        .ok_or_else(|| anyhow!("{} has no BodyScript", e.display_name()))
+}
+
+/// `None` when the module has no `EXECUTE AS` clause, which means `CALLER`
+fn execute_as(e: &RawElement) -> Result<Option<ExecuteAs>> {
+    if e.bool_property("IsCaller", false) {
+        Ok(Some(ExecuteAs::Caller))
+    } else if e.bool_property("IsOwner", false) {
+        Ok(Some(ExecuteAs::Owner))
+    } else if e.bool_property("IsSelf", false) {
+        Ok(Some(ExecuteAs::SelfUser))
+    } else {
+        Ok(e.object_reference("User")?.map(ExecuteAs::User))
+    }
+}
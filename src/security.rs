@@ -0,0 +1,216 @@
+//! Schemas, principals and permissions.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::element::RawElement;
+use crate::name::ObjectName;
+
+#[derive(Debug, PartialEq)]
+pub struct SqlSchema {
+    pub name: ObjectName,
+    /// The `AUTHORIZATION` principal, usually `[dbo]`. `None` when the model
+    /// doesn't give one.
+    pub authorizer: Option<ObjectName>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlUser {
+    pub name: ObjectName,
+    pub login: Option<ObjectName>,
+    pub authentication_type: AuthenticationType,
+    pub default_schema: Option<String>,
+    pub is_without_login: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlRole {
+    pub name: ObjectName,
+    pub authorizer: Option<ObjectName>,
+}
+
+/// Membership of a principal in a database role. These elements have no name.
+#[derive(Debug, PartialEq)]
+pub struct SqlRoleMembership {
+    pub role: ObjectName,
+    pub member: ObjectName,
+}
+
+/// A `GRANT`, `DENY` or `REVOKE` statement
+#[derive(Debug, PartialEq)]
+pub struct SqlPermissionStatement {
+    pub action: PermissionAction,
+    /// The permission name as DacFx spells it, e.g. `Select` or `ViewDefinition`
+    pub permission: String,
+    pub grantee: ObjectName,
+    pub grantor: Option<ObjectName>,
+    /// `None` for database level permissions
+    pub secured_object: Option<ObjectName>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AuthenticationType {
+    #[default]
+    None,
+    Instance,
+    Database,
+    Windows,
+    External,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionAction {
+    Grant,
+    GrantWithGrantOption,
+    Deny,
+    Revoke,
+}
+
+impl TryFrom<&RawElement> for SqlSchema {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlSchema {
+            authorizer: e.object_reference("Authorizer")?,
+            name: e.object_name("Schema")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlUser {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlUser {
+            login: e.object_reference("Login")?,
+            authentication_type: e.parse_property("AuthenticationType")?.unwrap_or_default(),
+            default_schema: e.object_reference("DefaultSchema")?.map(|s| s.name),
+            is_without_login: e.bool_property("WithoutLogin", false),
+            name: e.object_name("User")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlRole {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlRole {
+            authorizer: e.object_reference("Authorizer")?,
+            name: e.object_name("Role")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlRoleMembership {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlRoleMembership {
+            role: e.required_object_reference("Role")?,
+            member: e.required_object_reference("Member")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlPermissionStatement {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        // The statement is only spelled out in the name, which starts with
+        // `[Action.Permission.Class]`, e.g. `[Grant.Select.Object].[user]...`
+        let statement = e
+            .name
+            .as_deref()
+            .and_then(|n| n.strip_prefix('['))
+            .and_then(|n| n.split(']').next())
+            .ok_or_else(|| anyhow!("Permission statement has no Name attribute"))?;
+        let mut parts = statement.split('.');
+        let action = parts.next().unwrap_or_default().parse()?;
+        let permission = parts
+            .next()
+            .ok_or_else(|| anyhow!("No permission in statement {statement}"))?
+            .to_string();
+
+        Ok(SqlPermissionStatement {
+            action,
+            permission,
+            grantee: e.required_object_reference("Grantee")?,
+            grantor: e.object_reference("Grantor")?,
+            secured_object: e.object_reference("SecuredObject")?,
+        })
+    }
+}
+
+impl FromStr for AuthenticationType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "0" | "None" => Ok(AuthenticationType::None),
+            "1" | "Instance" => Ok(AuthenticationType::Instance),
+            "2" | "Database" => Ok(AuthenticationType::Database),
+            "3" | "Windows" => Ok(AuthenticationType::Windows),
+            "4" | "External" => Ok(AuthenticationType::External),
+            _ => Err(anyhow!("Unknown authentication type `{s}`")),
+        }
+    }
+}
+
+impl FromStr for PermissionAction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "Grant" => Ok(PermissionAction::Grant),
+            "GrantWithGrantOption" => Ok(PermissionAction::GrantWithGrantOption),
+            "Deny" => Ok(PermissionAction::Deny),
+            "Revoke" => Ok(PermissionAction::Revoke),
+            _ => Err(anyhow!("Unknown permission action `{s}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statement(name: &str, secured_object: &str) -> SqlPermissionStatement {
+        let xml = format!(
+            r#"<Element Type="SqlPermissionStatement" Name="{name}">
+              <Relationship Name="Grantee"><Entry><References Name="[u]" /></Entry></Relationship>
+              {secured_object}
+            </Element>"#
+        );
+        let e: RawElement = quick_xml::de::from_str(&xml).unwrap();
+        SqlPermissionStatement::try_from(&e).unwrap()
+    }
+
+    #[test]
+    fn permission_statement_names() {
+        let on_t = r#"<Relationship Name="SecuredObject"><Entry><References Name="[dbo].[T]" /></Entry></Relationship>"#;
+
+        let ps = statement("[Grant.Select.Object].[u].[dbo].[dbo].[T]", on_t);
+        assert_eq!(ps.action, PermissionAction::Grant);
+        assert_eq!(ps.permission, "Select");
+        assert_eq!(ps.grantee, "[u]".parse().unwrap());
+        assert_eq!(ps.secured_object, Some(ObjectName::new("dbo", "T")));
+
+        let ps = statement(
+            "[GrantWithGrantOption.Update.Object].[u].[dbo].[dbo].[T]",
+            on_t,
+        );
+        assert_eq!(ps.action, PermissionAction::GrantWithGrantOption);
+        assert_eq!(ps.permission, "Update");
+
+        let ps = statement("[Deny.Delete.Object].[u].[dbo].[dbo].[T]", on_t);
+        assert_eq!(ps.action, PermissionAction::Deny);
+        assert_eq!(ps.permission, "Delete");
+
+        let ps = statement("[Grant.ViewDefinition.Database].[u].[dbo]", "");
+        assert_eq!(ps.action, PermissionAction::Grant);
+        assert_eq!(ps.permission, "ViewDefinition");
+        assert_eq!(ps.secured_object, None);
+    }
+}
@@ -0,0 +1,59 @@
+//! Sequences.
+
+use anyhow::{Error, Result};
+
+use crate::element::RawElement;
+use crate::name::ObjectName;
+use crate::table::ElementTypeSpecifier;
+
+#[derive(Debug, PartialEq)]
+pub struct SqlSequence {
+    pub name: ObjectName,
+    pub schema: String,
+    /// `None` when the sequence was created without `AS`, i.e. `bigint`
+    pub type_specifier: Option<ElementTypeSpecifier>,
+    pub start_value: Option<i128>,
+    pub increment_value: i128,
+    /// `None` for `NO MINVALUE`, the minimum of the type
+    pub min_value: Option<i128>,
+    /// `None` for `NO MAXVALUE`, the maximum of the type
+    pub max_value: Option<i128>,
+    pub is_cycling: bool,
+    /// `false` for `NO CACHE`
+    pub is_cached: bool,
+    /// `None` when SQL Server picks the cache size
+    pub cache_size: Option<u64>,
+}
+
+impl TryFrom<&RawElement> for SqlSequence {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        let type_specifier = e
+            .elements("TypeSpecifier")
+            .first()
+            .map(|ts| ElementTypeSpecifier::try_from(*ts))
+            .transpose()?;
+        let min_value = match e.bool_property("NoMinValue", false) {
+            true => None,
+            false => e.parse_property("MinValue")?,
+        };
+        let max_value = match e.bool_property("NoMaxValue", false) {
+            true => None,
+            false => e.parse_property("MaxValue")?,
+        };
+
+        Ok(SqlSequence {
+            schema: e.schema()?,
+            type_specifier,
+            start_value: e.parse_property("StartValue")?,
+            increment_value: e.parse_property("IncrementValue")?.unwrap_or(1),
+            min_value,
+            max_value,
+            is_cycling: e.bool_property("IsCycling", false),
+            is_cached: !e.bool_property("NoCache", false),
+            cache_size: e.parse_property("CacheSize")?,
+            name: e.object_name("Sequence")?,
+        })
+    }
+}
@@ -1,49 +1,592 @@
+use crate::constraint::ForeignKeyAction;
+use crate::database::{Containment, PageVerifyMode, RecoveryMode};
+use crate::decimal::Decimal;
+use crate::external::{
+    ExternalDataSourceType, ExternalFileEncoding, ExternalFileFormatType, RejectType,
+};
+use crate::fulltext::ChangeTracking;
+use crate::index::{DataCompression, SecondaryXmlIndexType};
+use crate::name::ObjectName;
+use crate::programmability::{ExecuteAs, ParameterDirection, TriggerTiming};
+use crate::security::{AuthenticationType, PermissionAction};
+use crate::storage::PartitionRange;
+use crate::temporal;
+
 #[derive(Debug)]
 pub struct SimpleDacPacModel {
     pub tables: Vec<SimpleTable>,
-    //pub views: Vec<SimpleView>,
+    pub views: Vec<SimpleView>,
+    /// Table types, with the same shape as tables but no foreign keys
+    pub table_types: Vec<SimpleTable>,
+    pub user_defined_types: Vec<SimpleUserDefinedType>,
+    pub sequences: Vec<SimpleSequence>,
+    pub synonyms: Vec<SimpleSynonym>,
+    pub filegroups: Vec<SimpleFilegroup>,
+    pub partition_functions: Vec<SimplePartitionFunction>,
+    pub partition_schemes: Vec<SimplePartitionScheme>,
+    pub fulltext_catalogs: Vec<SimpleFullTextCatalog>,
+    pub fulltext_stop_lists: Vec<SimpleFullTextStopList>,
+    pub external_data_sources: Vec<SimpleExternalDataSource>,
+    pub external_file_formats: Vec<SimpleExternalFileFormat>,
+    pub external_tables: Vec<SimpleExternalTable>,
+    pub procedures: Vec<SimpleProcedure>,
+    pub functions: Vec<SimpleFunction>,
+    pub triggers: Vec<SimpleTrigger>,
+    pub security: SimpleSecurityModel,
+    /// `None` when the model has no `SqlDatabaseOptions` element
+    pub options: Option<SimpleDatabaseOptions>,
+    /// Elements of modelled types that failed to convert and are missing above
+    pub unconverted: Vec<SimpleUnconvertedElement>,
     // TODO: continue
 }
 
+/// An element left out of the model, see [`crate::ElementEnum::Unknown`]
+#[derive(Debug)]
+pub struct SimpleUnconvertedElement {
+    pub ty: String,
+    pub name: Option<String>,
+    pub error: String,
+}
+
+/// `ALTER DATABASE ... SET` options
+#[derive(Debug)]
+pub struct SimpleDatabaseOptions {
+    pub collation: Option<String>,
+    pub compatibility_level: Option<u16>,
+    pub recovery_model: Option<RecoveryMode>,
+    pub containment: Containment,
+    pub page_verify: Option<PageVerifyMode>,
+    pub read_committed_snapshot: bool,
+    pub allow_snapshot_isolation: bool,
+    pub memory_optimized_elevate_to_snapshot: bool,
+    pub ansi_null_default: bool,
+    pub ansi_nulls: bool,
+    pub ansi_padding: bool,
+    pub ansi_warnings: bool,
+    pub arithabort: bool,
+    pub concat_null_yields_null: bool,
+    pub numeric_roundabort: bool,
+    pub quoted_identifier: bool,
+    pub recursive_triggers: bool,
+    pub auto_close: bool,
+    pub auto_shrink: bool,
+    pub auto_create_statistics: bool,
+    pub auto_update_statistics: bool,
+    pub auto_update_statistics_async: bool,
+    pub trustworthy: bool,
+    pub db_chaining: bool,
+    pub full_text_enabled: bool,
+    pub default_language: Option<String>,
+    pub default_full_text_language: Option<String>,
+    /// Name and value of the properties without a field above
+    pub other: Vec<(String, String)>,
+}
+
+/// Principals and permissions of a database
+#[derive(Debug, Default)]
+pub struct SimpleSecurityModel {
+    pub schemas: Vec<SimpleSchema>,
+    pub users: Vec<SimpleUser>,
+    pub roles: Vec<SimpleRole>,
+    pub role_memberships: Vec<SimpleRoleMembership>,
+    pub permissions: Vec<SimplePermission>,
+}
+
+#[derive(Debug)]
+pub struct SimpleSchema {
+    pub name: String,
+    pub owner: String,
+}
+
+#[derive(Debug)]
+pub struct SimpleUser {
+    pub name: String,
+    pub login: Option<String>,
+    pub authentication: AuthenticationType,
+    pub default_schema: Option<String>,
+    pub without_login: bool,
+}
+
+#[derive(Debug)]
+pub struct SimpleRole {
+    pub name: String,
+    pub owner: String,
+}
+
+#[derive(Debug)]
+pub struct SimpleRoleMembership {
+    pub role: String,
+    pub member: String,
+}
+
+#[derive(Debug)]
+pub struct SimplePermission {
+    pub action: PermissionAction,
+    pub permission: String,
+    pub grantee: String,
+    /// `None` for database level permissions
+    pub securable: Option<ObjectName>,
+}
+
 #[derive(Debug)]
 pub struct SimpleTable {
+    pub name: ObjectName,
+    pub columns: Vec<SimpleTableColumn>,
+    pub primary_key: Option<SimplePrimaryKey>,
+    pub foreign_keys: Vec<SimpleForeignKey>,
+    pub unique_constraints: Vec<SimpleUniqueConstraint>,
+    pub check_constraints: Vec<SimpleCheckConstraint>,
+    pub indexes: Vec<SimpleIndex>,
+    pub storage: SimpleStorage,
+    /// `TEXTIMAGE_ON`, where LOB data is stored when not with the rows
+    pub text_image_filegroup: Option<String>,
+    pub fulltext_index: Option<SimpleFullTextIndex>,
+}
+
+/// Where the rows of a table are stored
+#[derive(Debug, Default)]
+pub enum SimpleStorage {
+    #[default]
+    DefaultFilegroup,
+    Filegroup(String),
+    PartitionScheme {
+        scheme: String,
+        column: String,
+    },
+}
+
+#[derive(Debug)]
+pub struct SimpleFilegroup {
     pub name: String,
+    pub memory_optimized: bool,
+    pub filestream: bool,
+}
+
+#[derive(Debug)]
+pub struct SimplePartitionFunction {
+    pub name: String,
+    pub parameter_type: SimpleColumnType,
+    pub range: PartitionRange,
+    /// Boundary values, as written in the source, e.g. `N'2024-01-01'`
+    pub boundaries: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct SimplePartitionScheme {
+    pub name: String,
+    pub function: String,
+    /// Filegroups of the partitions, in order
+    pub filegroups: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct SimpleFullTextCatalog {
+    pub name: String,
+    pub accent_sensitive: bool,
+    /// Whether this is the catalog of indexes without an explicit one
+    pub default: bool,
+    pub owner: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct SimpleFullTextStopList {
+    pub name: String,
+    pub owner: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct SimpleFullTextIndex {
+    pub columns: Vec<SimpleFullTextColumn>,
+    /// Name of the unique index used as the fulltext key
+    pub key_index: String,
+    pub catalog: Option<String>,
+    pub filegroup: Option<String>,
+    pub stop_list: SimpleStopList,
+    pub change_tracking: ChangeTracking,
+}
+
+#[derive(Debug)]
+pub struct SimpleFullTextColumn {
+    pub name: String,
+    /// LCID of the word breaker, `None` for the default fulltext language
+    pub language: Option<u32>,
+    pub type_column: Option<String>,
+    pub statistical_semantics: bool,
+}
+
+#[derive(Debug)]
+pub enum SimpleStopList {
+    System,
+    Off,
+    Named(String),
+}
+
+#[derive(Debug)]
+pub struct SimpleExternalDataSource {
+    pub name: String,
+    pub ty: ExternalDataSourceType,
+    pub location: String,
+    pub credential: Option<String>,
+    pub resource_manager_location: Option<String>,
+    pub database_name: Option<String>,
+    pub shard_map_name: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct SimpleExternalFileFormat {
+    pub name: String,
+    pub ty: ExternalFileFormatType,
+    pub field_terminator: Option<String>,
+    pub string_delimiter: Option<String>,
+    pub date_format: Option<String>,
+    pub use_type_default: bool,
+    pub first_row: Option<u32>,
+    pub encoding: Option<ExternalFileEncoding>,
+    /// The codec class, e.g. `org.apache.hadoop.io.compress.GzipCodec`
+    pub compression: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct SimpleExternalTable {
+    pub name: ObjectName,
     pub columns: Vec<SimpleTableColumn>,
+    pub data_source: String,
+    pub file_format: Option<String>,
+    pub location: Option<String>,
+    /// `None` when no reject options are given
+    pub reject: Option<SimpleRejectOptions>,
 }
 
 #[derive(Debug)]
-pub struct SimpleTableColumn {
+pub struct SimpleRejectOptions {
+    pub ty: RejectType,
+    pub value: f64,
+    pub sample_value: Option<f64>,
+    pub rejected_row_location: Option<String>,
+}
+
+/// An alias type
+#[derive(Debug)]
+pub struct SimpleUserDefinedType {
+    pub name: ObjectName,
+    pub base: SimpleColumnType,
+    pub nullable: bool,
+}
+
+#[derive(Debug)]
+pub struct SimpleSequence {
+    pub name: ObjectName,
+    pub ty: SimpleColumnType,
+    pub start: Option<i128>,
+    pub increment: i128,
+    /// `None` for `NO MINVALUE`
+    pub min: Option<i128>,
+    /// `None` for `NO MAXVALUE`
+    pub max: Option<i128>,
+    pub cycle: bool,
+    /// `false` for `NO CACHE`
+    pub cached: bool,
+    pub cache_size: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct SimpleSynonym {
+    pub name: ObjectName,
+    /// The parts of the target name, up to four as it may be on another
+    /// server or in another database
+    pub target: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct SimpleView {
+    pub name: ObjectName,
+    /// The `SELECT` statement of the view
+    pub definition: String,
+    pub schema_bound: bool,
+    pub with_check_option: bool,
+    pub columns: Vec<SimpleViewColumn>,
+    pub dependencies: Vec<ObjectName>,
+    /// Indexes of an indexed view
+    pub indexes: Vec<SimpleIndex>,
+    pub fulltext_index: Option<SimpleFullTextIndex>,
+}
+
+#[derive(Debug)]
+pub struct SimpleViewColumn {
+    pub name: String,
+    /// Names of the columns the value comes from
+    pub sources: Vec<ObjectName>,
+    /// The type of the source column when the value depends on a single table
+    /// column. The model doesn't say whether the expression changes it.
+    pub ty: Option<SimpleColumnType>,
+}
+
+#[derive(Debug)]
+pub struct SimpleProcedure {
+    pub name: ObjectName,
+    pub parameters: Vec<SimpleParameter>,
+    pub body: String,
+    pub execute_as: Option<ExecuteAs>,
+}
+
+#[derive(Debug)]
+pub struct SimpleFunction {
+    pub name: ObjectName,
+    pub kind: SimpleFunctionKind,
+    pub parameters: Vec<SimpleParameter>,
+    pub body: String,
+    pub execute_as: Option<ExecuteAs>,
+}
+
+#[derive(Debug)]
+pub enum SimpleFunctionKind {
+    /// `RETURNS` type, alias types being resolved to their base type
+    Scalar(SimpleColumnType),
+    InlineTableValued,
+    MultiStatementTableValued,
+}
+
+#[derive(Debug)]
+pub struct SimpleTrigger {
+    pub name: ObjectName,
+    /// The table or view the trigger is defined on
+    pub table: ObjectName,
+    pub timing: TriggerTiming,
+    pub on_insert: bool,
+    pub on_update: bool,
+    pub on_delete: bool,
+    pub not_for_replication: bool,
+    pub body: String,
+    pub execute_as: Option<ExecuteAs>,
+}
+
+/// A parameter of a procedure or a function
+#[derive(Debug)]
+pub struct SimpleParameter {
     pub name: String,
+    /// The declared type, e.g. an alias or a table type
+    pub type_name: ObjectName,
+    /// Alias types are resolved to their base type, table types are `Other`
     pub ty: SimpleColumnType,
-    pub nullable: bool,
+    pub direction: ParameterDirection,
     pub default: Option<String>,
-    //pub identity: bool,
-    //pub computed: bool,
-    //pub primary_key: bool,
-    //pub unique: bool,
-    //pub check: Option<String>,
-    //pub foreign_key: Option<SimpleForeignKey>,
+    pub read_only: bool,
+}
+
+#[derive(Debug)]
+pub struct SimpleTableColumn {
+    pub name: String,
+    /// `None` for computed columns whose type the model doesn't give and that
+    /// isn't given by a top-level `CAST`/`CONVERT` or a column of the table
+    pub ty: Option<SimpleColumnType>,
+    /// The alias type of the column, `ty` then being its base type
+    pub user_type: Option<ObjectName>,
+    pub nullable: bool,
+    /// `None` when the column uses the database collation
+    pub collation: Option<String>,
+    pub default: Option<SimpleDefault>,
+    pub identity: Option<SimpleIdentity>,
+    pub computed: Option<SimpleComputedColumn>,
+}
+
+/// `IDENTITY(seed, increment)` of a column
+#[derive(Debug)]
+pub struct SimpleIdentity {
+    pub seed: i128,
+    pub increment: i128,
+    pub not_for_replication: bool,
+}
+
+#[derive(Debug)]
+pub struct SimpleComputedColumn {
+    pub expression: String,
+    /// Only persisted computed columns have data in a bacpac
+    pub persisted: bool,
+    /// Names of the columns and objects used by the expression
+    pub dependencies: Vec<ObjectName>,
+}
+
+#[derive(Debug)]
+pub struct SimpleDefault {
+    pub name: Option<ObjectName>,
+    pub expression: String,
+}
+
+#[derive(Debug)]
+pub struct SimplePrimaryKey {
+    pub name: Option<ObjectName>,
+    pub columns: Vec<SimpleIndexColumn>,
+    pub clustered: bool,
+    pub fill_factor: Option<u8>,
+}
+
+#[derive(Debug)]
+pub struct SimpleForeignKey {
+    pub name: Option<ObjectName>,
+    pub columns: Vec<String>,
+    pub foreign_table: ObjectName,
+    pub foreign_columns: Vec<String>,
+    pub on_delete: ForeignKeyAction,
+    pub on_update: ForeignKeyAction,
+    pub not_for_replication: bool,
+    pub with_no_check: bool,
+}
+
+#[derive(Debug)]
+pub struct SimpleUniqueConstraint {
+    pub name: Option<ObjectName>,
+    pub columns: Vec<SimpleIndexColumn>,
+    pub clustered: bool,
+    pub filegroup: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct SimpleCheckConstraint {
+    pub name: Option<ObjectName>,
+    pub expression: String,
+    pub not_for_replication: bool,
+    /// `false` for constraints created `WITH NOCHECK`
+    pub checked: bool,
+}
+
+#[derive(Debug)]
+pub struct SimpleIndex {
+    pub name: String,
+    pub kind: SimpleIndexKind,
+    /// Key columns, or the covered columns of a nonclustered columnstore index
+    pub columns: Vec<SimpleIndexColumn>,
+    pub included_columns: Vec<String>,
+    /// The `WHERE` clause of a filtered index
+    pub filter: Option<String>,
+    pub unique: bool,
+    pub clustered: bool,
+    pub fill_factor: Option<u8>,
+    pub disabled: bool,
+    pub data_compression: Vec<SimpleDataCompression>,
+    pub filegroup: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum SimpleIndexKind {
+    RowStore,
+    ColumnStore,
+    Xml {
+        primary: bool,
+        secondary_type: Option<SecondaryXmlIndexType>,
+    },
+    Spatial,
 }
 
+#[derive(Debug)]
+pub struct SimpleDataCompression {
+    pub level: DataCompression,
+    /// `None` when the level applies to all partitions
+    pub partition: Option<u32>,
+}
+
+/// A key column of a constraint or an index
+#[derive(Debug)]
+pub struct SimpleIndexColumn {
+    pub name: String,
+    pub descending: bool,
+}
+
+/// A column type with its facets
 #[derive(Debug, Clone, PartialEq)]
 pub enum SimpleColumnType {
+    Bit,
+    TinyInt,
+    SmallInt,
     Int,
     BigInt,
-    Nvarchar(i32),
-    Varchar(i32),
+    /// `decimal(precision, scale)`
+    Decimal(i8, i8),
+    /// `numeric(precision, scale)`
+    Numeric(i8, i8),
+    Money,
+    SmallMoney,
+    /// `float(n)`, `n` being the number of mantissa bits
+    Float(i8),
+    Real,
+    Date,
+    Time(i8),
+    DateTime,
+    SmallDateTime,
     DateTime2(i8),
+    DateTimeOffset(i8),
+    Char(SimpleColumnLength),
+    Varchar(SimpleColumnLength),
+    Text,
+    Nchar(SimpleColumnLength),
+    Nvarchar(SimpleColumnLength),
+    Ntext,
+    Binary(SimpleColumnLength),
+    Varbinary(SimpleColumnLength),
+    Image,
+    UniqueIdentifier,
+    Xml,
+    SqlVariant,
+    HierarchyId,
+    Geography,
+    Geometry,
+    RowVersion,
+    /// A type without a variant of its own: CLR and table types, unresolved
+    /// alias types or built-in types this crate doesn't know
+    Other(ObjectName),
 }
 
-#[derive(Debug)]
+/// Declared length of a character or binary column, in characters for
+/// character types and in bytes for binary types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimpleColumnLength {
+    /// `(n)`
+    Bounded(i32),
+    /// `(max)`
+    Max,
+    /// No length in the model, SQL Server's default of 1 applies
+    Unspecified,
+}
+
+#[derive(Debug, PartialEq)]
 pub enum SimpleColumnValue {
+    Null,
+    Bit(bool),
+    TinyInt(u8),
+    SmallInt(i16),
     Int(i32),
     BigInt(i64),
-    Nvarchar(String),
+    Decimal(Decimal),
+    Numeric(Decimal),
+    Money(Decimal),
+    SmallMoney(Decimal),
+    Float(f64),
+    Real(f32),
+    Date(temporal::Date),
+    Time(temporal::Time),
+    DateTime(temporal::DateTime),
+    SmallDateTime(temporal::DateTime),
+    DateTime2(temporal::DateTime),
+    DateTimeOffset(temporal::DateTimeOffset),
+    Char(String),
     Varchar(String),
-    DateTime2(String),
-}
-
-pub fn remove_delimiters(name: &String) -> String {
-    //This shit works.. somewhat.. To fix it.. someday
-    name.replace("[", "").replace("]", "")
+    Text(String),
+    Nchar(String),
+    Nvarchar(String),
+    Ntext(String),
+    Binary(Vec<u8>),
+    Varbinary(Vec<u8>),
+    Image(Vec<u8>),
+    UniqueIdentifier(String),
+    Xml(String),
+    /// Raw `sql_variant` value: base type, property bytes and data
+    SqlVariant(Vec<u8>),
+    /// Serialized `hierarchyid`
+    HierarchyId(Vec<u8>),
+    /// Serialized `geography`
+    Geography(Vec<u8>),
+    /// Serialized `geometry`
+    Geometry(Vec<u8>),
+    RowVersion(Vec<u8>),
+    /// Serialized value of a CLR type
+    Other(Vec<u8>),
 }
@@ -0,0 +1,115 @@
+//! Filegroups and partitioning.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::element::RawElement;
+use crate::name::ObjectName;
+use crate::table::ElementTypeSpecifier;
+
+#[derive(Debug, PartialEq)]
+pub struct SqlFilegroup {
+    pub name: ObjectName,
+    pub contains_memory_optimized_data: bool,
+    pub contains_file_stream: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlPartitionFunction {
+    pub name: ObjectName,
+    pub parameter_type: ElementTypeSpecifier,
+    pub range: PartitionRange,
+    /// Boundary values, as written in the source
+    pub boundary_values: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlPartitionScheme {
+    pub name: ObjectName,
+    pub partition_function: ObjectName,
+    /// Filegroups of the partitions, in order
+    pub filegroups: Vec<ObjectName>,
+}
+
+/// Which side of a boundary value the value itself belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PartitionRange {
+    #[default]
+    Left,
+    Right,
+}
+
+impl TryFrom<&RawElement> for SqlFilegroup {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlFilegroup {
+            contains_memory_optimized_data: e.bool_property("ContainsMemoryOptimizedData", false),
+            contains_file_stream: e.bool_property("ContainsFileStream", false),
+            name: e.object_name("Filegroup")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlPartitionFunction {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        let parameter_type = e
+            .elements("ParameterType")
+            .first()
+            .map(|ts| ElementTypeSpecifier::try_from(*ts))
+            .transpose()?
+            .ok_or_else(|| anyhow!("{} has no ParameterType", e.display_name()))?;
+        let boundary_values = e
+            .elements("BoundaryValues")
+            .into_iter()
+            .map(|v| {
+                v.property_value("ExpressionScript").ok_or_else(|| {
+                    anyhow!(
+                        "Boundary value of {} has no ExpressionScript",
+                        e.display_name()
+                    )
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(SqlPartitionFunction {
+            parameter_type,
+            range: e.parse_property("Range")?.unwrap_or_default(),
+            boundary_values,
+            name: e.object_name("Partition function")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlPartitionScheme {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        let filegroups = e
+            .elements("FilegroupSpecifiers")
+            .into_iter()
+            .map(|s| s.required_object_reference("Filegroup"))
+            .collect::<Result<_>>()?;
+
+        Ok(SqlPartitionScheme {
+            partition_function: e.required_object_reference("PartitionFunction")?,
+            filegroups,
+            name: e.object_name("Partition scheme")?,
+        })
+    }
+}
+
+impl FromStr for PartitionRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "0" | "1" | "Left" => Ok(PartitionRange::Left),
+            "2" | "Right" => Ok(PartitionRange::Right),
+            _ => Err(anyhow!("Unknown partition range `{s}`")),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Synonyms.
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::element::RawElement;
+use crate::name::{parse_multipart, ObjectName};
+
+#[derive(Debug, PartialEq)]
+pub struct SqlSynonym {
+    pub name: ObjectName,
+    pub schema: String,
+    /// The parts of the name of the object the synonym stands for, up to
+    /// four. It may live in another database, so it isn't necessarily an
+    /// element of the model.
+    pub for_object: Vec<String>,
+}
+
+impl TryFrom<&RawElement> for SqlSynonym {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        let for_object = e
+            .reference("ForObject")
+            .or_else(|| e.property_value("ForObjectScript"))
+            .ok_or_else(|| anyhow!("Synonym {} has no ForObject", e.display_name()))?;
+        let for_object = parse_multipart(&for_object)?;
+
+        Ok(SqlSynonym {
+            schema: e.schema()?,
+            for_object,
+            name: e.object_name("Synonym")?,
+        })
+    }
+}
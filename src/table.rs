@@ -1,256 +1,298 @@
-use std::fmt;
+use anyhow::{anyhow, bail};
 
-use serde::de::value::MapAccessDeserializer;
-use serde::de::{Error, MapAccess, Visitor};
-use serde::Deserialize;
-use tracing::{debug, error, field, span, Level};
+use crate::element::{Annotation, RawElement, Reference};
+use crate::name::ObjectName;
 
 #[derive(Debug, PartialEq)]
 pub struct SqlTable {
-    pub name: String,
-    pub properties: Option<crate::Property>,
+    pub name: ObjectName,
+    pub properties: Vec<crate::Property>,
     pub columns_relationship: SqlTableColumnRelationship,
-    pub schema_relationship: SqlTableSchemaRelationship,
+    /// The name of the schema of the table
+    pub schema_relationship: String,
+    /// `ON filegroup`, `None` for the default filegroup or a partition scheme
+    pub filegroup: Option<ObjectName>,
+    /// `ON scheme(column)`
+    pub partition_scheme: Option<ObjectName>,
+    pub partition_column: Option<ObjectName>,
+    /// `TEXTIMAGE_ON filegroup`
+    pub text_image_filegroup: Option<ObjectName>,
 }
 
-#[derive(Debug, PartialEq)]
-enum ColumnsOrSchema {
-    Columns(SqlTableColumnRelationship),
-    Schema(SqlTableSchemaRelationship),
-}
-
-impl<'de> Deserialize<'de> for SqlTable {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        struct SqlTableVisitor;
-
-        impl<'de> Visitor<'de> for SqlTableVisitor {
-            type Value = SqlTable;
+impl TryFrom<&RawElement> for SqlTable {
+    type Error = anyhow::Error;
 
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("an object with a `type` field")
-            }
+    fn try_from(e: &RawElement) -> anyhow::Result<Self> {
+        let name = e.object_name("SqlTable")?;
+        let schema = e
+            .schema()
+            .map_err(|err| anyhow!("No Schema for table {name}: {err}"))?;
 
-            fn visit_map<A>(self, mut map: A) -> Result<SqlTable, A::Error>
-            where
-                A: MapAccess<'de>,
-            {
-                let span = span!(Level::INFO, "SqlTable", name = field::Empty);
-                let _guard = span.enter();
-
-                let mut name: Option<String> = None;
-                let mut columns: SqlTableColumnRelationship =
-                    SqlTableColumnRelationship { entry: Vec::new() };
-                let mut schema: Option<SqlTableSchemaRelationship> = None;
-                let mut prop: Option<crate::Property> = None;
-
-                while let Some(key) = &map.next_key::<String>()? {
-                    match key.as_str() {
-                        "Property" => {
-                            prop = Some(map.next_value::<crate::Property>()?);
-                            debug!("Property: {:#?}", prop);
-                        }
-                        "@Name" => {
-                            let table_name = map.next_value::<String>()?;
-                            span.record("name", table_name.clone());
-                            debug!("Name: {:#?}", table_name);
-                            name = Some(table_name);
-                        }
-                        "Relationship" => {
-                            let span = span!(Level::INFO, "Relationship");
-                            let _guard = span.enter();
-                            debug!("Relationship >>>");
-
-                            let xx = map.next_value::<ColumnsOrSchema>()?;
-                            debug!("<<<ColumnsOrSchema: {:#?}", xx);
-                            match xx {
-                                ColumnsOrSchema::Columns(c) => {
-                                    columns = c;
-                                }
-                                ColumnsOrSchema::Schema(s) => {
-                                    schema = Some(s);
-                                }
-                            }
-                        }
-                        _ => {
-                            let value = map.next_value::<String>()?;
-                            error!("Unknown key: {:#?} -> {:#?}", key, value);
-                        }
-                    }
-                }
-                debug!("Returning");
-
-                match (name, schema) {
-                    (Some(name), Some(schema)) => Ok(SqlTable {
-                        name,
-                        properties: prop,
-                        columns_relationship: columns,
-                        schema_relationship: schema,
-                    }),
-                    (None, _) => Err(Error::custom("SqlTable has no Name attribute")),
-                    (Some(name), None) => Err(Error::custom(format!("No Schema for table {name}"))),
-                }
-            }
+        let mut entry = Vec::new();
+        for column in e.elements("Columns") {
+            entry.push(SqlTableColumnRelationshipEntry {
+                element: SqlTableColumnElement::try_from(column)?,
+            });
         }
-        deserializer.deserialize_map(SqlTableVisitor)
+
+        Ok(SqlTable {
+            name,
+            columns_relationship: SqlTableColumnRelationship { entry },
+            filegroup: e.object_reference("Filegroup")?,
+            partition_scheme: e.object_reference("PartitionScheme")?,
+            partition_column: e.object_reference("PartitionColumn")?,
+            text_image_filegroup: e.object_reference("FilegroupForTextImage")?,
+            properties: e.properties.clone(),
+            schema_relationship: schema,
+        })
     }
 }
 
-impl<'de> Deserialize<'de> for ColumnsOrSchema {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        struct ColumnsOrSchemaVisitor;
+#[derive(Debug, PartialEq)]
+pub struct SqlTableColumnRelationship {
+    pub entry: Vec<SqlTableColumnRelationshipEntry>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SqlTableColumnRelationshipEntry {
+    pub element: SqlTableColumnElement,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SqlTableColumnElement {
+    Simple(SqlSimpleColumnTableElement),
+    Computed(SqlComputedColumnTableElement),
+}
 
-        impl<'de> Visitor<'de> for ColumnsOrSchemaVisitor {
-            type Value = ColumnsOrSchema;
+impl TryFrom<&RawElement> for SqlTableColumnElement {
+    type Error = anyhow::Error;
 
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("a ColumnsOrSchema object")
+    fn try_from(e: &RawElement) -> anyhow::Result<Self> {
+        match e.ty.as_str() {
+            "SqlSimpleColumn" | "SqlTableTypeSimpleColumn" => {
+                Ok(SqlTableColumnElement::Simple(e.try_into()?))
             }
-
-            fn visit_map<A>(self, mut map: A) -> Result<ColumnsOrSchema, A::Error>
-            where
-                A: MapAccess<'de>,
-            {
-                let span = span!(Level::INFO, "ColumnsOrSchema");
-                let _guard = span.enter();
-
-                while let Some((key, value)) = map.next_entry::<String, String>()? {
-                    let mad = MapAccessDeserializer::new(&mut map);
-                    match key.as_str() {
-                        "@Name" => {
-                            match value.as_str() {
-                                "Columns" => {
-                                    debug!("Columns");
-                                    let f = SqlTableColumnRelationship::deserialize(mad)?;
-                                    debug!("Columns: {:#?}", f);
-                                    return Ok(ColumnsOrSchema::Columns(f));
-                                }
-                                "Schema" => {
-                                    debug!("Schema");
-                                    let schema = SqlTableSchemaRelationship::deserialize(mad)?;
-                                    return Ok(ColumnsOrSchema::Schema(schema));
-                                }
-                                _ => {
-                                    return Err(Error::custom(format!("Unknown Name={value}. Expected either `Columns` or `Schema`")));
-                                }
-                            }
-                        }
-                        _ => continue,
-                    }
-                }
-                error!("Didn't return because no Columns or Schema");
-                Err(Error::custom(
-                    "expected `Name` attribute on Relationship element",
-                ))
+            "SqlComputedColumn" | "SqlTableTypeComputedColumn" => {
+                Ok(SqlTableColumnElement::Computed(e.try_into()?))
             }
+            ty => bail!("Unknown column type {ty} for {}", e.display_name()),
         }
-        deserializer.deserialize_map(ColumnsOrSchemaVisitor)
     }
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(deny_unknown_fields)]
-pub struct SqlTableColumnRelationship {
-    #[serde(rename = "Entry")]
-    pub entry: Vec<SqlTableColumnRelationshipEntry>,
+#[derive(Debug, PartialEq)]
+pub struct SqlSimpleColumnTableElement {
+    pub name: ObjectName,
+    pub properties: Vec<crate::Property>,
+    pub type_specifier: ElementTypeSpecifier,
+    pub identity_specifier: Option<SqlColumnIdentitySpecifier>,
+    pub annotations: Vec<Annotation>,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(deny_unknown_fields)]
-pub struct SqlTableColumnRelationshipEntry {
-    #[serde(rename = "Element")]
-    pub element: SqlSimpleColumnTableElement,
+impl SqlSimpleColumnTableElement {
+    /// Returns the value of the `Property` named `name`, if any
+    pub fn property(&self, name: &str) -> Option<String> {
+        self.properties
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.get_value())
+    }
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-//#[serde(deny_unknown_fields)]
-pub struct SqlSimpleColumnTableElement {
-    #[serde(rename = "@Name")]
-    pub name: String,
-    #[serde(rename = "Property")]
-    pub properties: Option<Vec<crate::Property>>,
-    #[serde(rename = "Relationship")]
-    pub relationship: SqlSimpleColumnRelationshipEntry,
-    #[serde(rename = "Annotation")]
-    pub annotation: Option<SqlSimpleColumnAnnotation>,
+impl TryFrom<&RawElement> for SqlSimpleColumnTableElement {
+    type Error = anyhow::Error;
+
+    fn try_from(e: &RawElement) -> anyhow::Result<Self> {
+        let name = e.object_name(&format!("{} column", e.ty))?;
+        let type_specifier = match e.elements("TypeSpecifier").first() {
+            Some(ts) => ElementTypeSpecifier::try_from(*ts)?,
+            None => bail!("Column {name} has no TypeSpecifier"),
+        };
+        let identity_specifier = match e.elements("IdentitySpecifier").first() {
+            Some(is) => Some(SqlColumnIdentitySpecifier::try_from(*is)?),
+            None => None,
+        };
+
+        Ok(SqlSimpleColumnTableElement {
+            name,
+            properties: e.properties.clone(),
+            type_specifier,
+            identity_specifier,
+            annotations: e.annotations.clone(),
+        })
+    }
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(deny_unknown_fields)]
-pub struct SqlSimpleColumnAnnotation {
-    #[serde(rename = "@Name")]
-    pub name: String,
-    #[serde(rename = "@Type")]
-    pub ty: String,
-    #[serde(rename = "Property")]
-    pub property: crate::Property,
+/// A column computed from an expression. The model only gives its type when
+/// it was declared explicitly, which is never the case for T-SQL tables.
+#[derive(Debug, PartialEq)]
+pub struct SqlComputedColumnTableElement {
+    pub name: ObjectName,
+    pub properties: Vec<crate::Property>,
+    pub expression: String,
+    pub is_persisted: bool,
+    /// Names of the columns and objects used by the expression
+    pub dependencies: Vec<ObjectName>,
+    pub type_specifier: Option<ElementTypeSpecifier>,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(deny_unknown_fields)]
-pub struct SqlSimpleColumnRelationshipEntry {
-    #[serde(rename = "@Name")]
-    pub name: String,
-    #[serde(rename = "Entry")]
-    pub entry: SqlSimpleColumnRelationshipEntryReference,
+impl SqlComputedColumnTableElement {
+    /// Returns the value of the `Property` named `name`, if any
+    pub fn property(&self, name: &str) -> Option<String> {
+        self.properties
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.get_value())
+    }
 }
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(deny_unknown_fields)]
-pub struct SqlSimpleColumnRelationshipEntryReference {
-    #[serde(rename = "Element")]
-    pub element_type_specifier: ElementTypeSpecifier,
+
+impl TryFrom<&RawElement> for SqlComputedColumnTableElement {
+    type Error = anyhow::Error;
+
+    fn try_from(e: &RawElement) -> anyhow::Result<Self> {
+        let name = e.object_name(&format!("{} column", e.ty))?;
+        let expression = e
+            .property_value("ExpressionScript")
+            .ok_or_else(|| anyhow!("Computed column {name} has no ExpressionScript"))?;
+        let type_specifier = match e.elements("TypeSpecifier").first() {
+            Some(ts) => Some(ElementTypeSpecifier::try_from(*ts)?),
+            None => None,
+        };
+
+        Ok(SqlComputedColumnTableElement {
+            name,
+            expression,
+            is_persisted: e.bool_property("IsPersisted", false),
+            dependencies: e.object_references("ExpressionDependencies")?,
+            type_specifier,
+            properties: e.properties.clone(),
+        })
+    }
 }
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(deny_unknown_fields)]
+
+/// The `SqlTypeSpecifier` of a column: the referenced type with its facets
+#[derive(Debug, PartialEq)]
 pub struct ElementTypeSpecifier {
-    #[serde(rename = "@Type")]
     pub ty: String,
-    #[serde(rename = "Property")]
-    pub property: Option<crate::Property>,
-    #[serde(rename = "Relationship")]
-    pub type_specifier_rela: TypeSpecifierRelationship,
+    pub properties: Vec<crate::Property>,
+    pub type_reference: Reference,
+    /// The parsed name of the type, without a schema for built-in types
+    pub type_name: ObjectName,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(deny_unknown_fields)]
-pub struct TypeSpecifierRelationship {
-    #[serde(rename = "@Name")]
-    pub name: String,
-    #[serde(rename = "Entry")]
-    pub entry: TypeSpecifierRelationshipEntry,
+impl TryFrom<&RawElement> for ElementTypeSpecifier {
+    type Error = anyhow::Error;
+
+    fn try_from(e: &RawElement) -> anyhow::Result<Self> {
+        let type_reference = e
+            .relationship("Type")
+            .and_then(|r| r.entries.first())
+            .and_then(|entry| entry.references.as_ref())
+            .ok_or_else(|| anyhow!("{} has no Type reference", e.ty))?;
+
+        Ok(ElementTypeSpecifier {
+            ty: e.ty.clone(),
+            properties: e.properties.clone(),
+            type_name: type_reference.name.parse()?,
+            type_reference: type_reference.clone(),
+        })
+    }
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(deny_unknown_fields)]
-pub struct TypeSpecifierRelationshipEntry {
-    #[serde(rename = "References")]
-    pub element: SqlTableReference,
+/// Parses a type written in T-SQL, such as `decimal(10, 2)` or `[varchar](max)`,
+/// into the facets DacFx would have given it
+impl std::str::FromStr for ElementTypeSpecifier {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let s = s.trim();
+        let (name, args) = match s.find('(') {
+            Some(i) => {
+                let args = s[i + 1..]
+                    .strip_suffix(')')
+                    .ok_or_else(|| anyhow!("Invalid type `{s}`"))?;
+                (s[..i].trim_end(), args.split(',').map(str::trim).collect())
+            }
+            None => (s, Vec::new()),
+        };
+
+        let mut type_name: ObjectName = name.parse()?;
+        if type_name.schema.is_none() {
+            type_name.name = type_name.name.to_lowercase();
+        }
+        let facets: &[&str] = match type_name.name.as_str() {
+            "decimal" | "numeric" => &["Precision", "Scale"],
+            "float" => &["Precision"],
+            "time" | "datetime2" | "datetimeoffset" => &["Scale"],
+            _ => &["Length"],
+        };
+        if args.len() > facets.len() {
+            bail!("Too many arguments in type `{s}`");
+        }
+
+        let properties = facets
+            .iter()
+            .zip(args)
+            .map(|(facet, value)| {
+                let (name, value) = match value {
+                    v if v.eq_ignore_ascii_case("max") => ("IsMax", "True"),
+                    v => (*facet, v),
+                };
+                crate::Property {
+                    name: name.to_string(),
+                    value: Some(value.to_string()),
+                    cdata: None,
+                }
+            })
+            .collect();
+
+        Ok(ElementTypeSpecifier {
+            ty: "SqlTypeSpecifier".to_string(),
+            properties,
+            type_reference: Reference {
+                name: type_name.to_string(),
+                external_source: None,
+                disambiguator: None,
+            },
+            type_name,
+        })
+    }
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(deny_unknown_fields)]
-pub struct SqlTableReference {
-    #[serde(rename = "@Name")]
-    pub name: String,
-    #[serde(rename = "@ExternalSource")]
-    pub external_source: String,
+impl ElementTypeSpecifier {
+    /// Returns the value of the `Property` named `name`, if any
+    pub fn property(&self, name: &str) -> Option<String> {
+        self.properties
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.get_value())
+    }
+
+    /// Returns a numeric facet (`Length`, `Precision`, `Scale`...), or
+    /// `default` when DacFx left it out because it has the type's default value
+    pub fn facet<T: std::str::FromStr>(&self, name: &str, default: T) -> T {
+        self.property(name)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default)
+    }
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(deny_unknown_fields)]
-pub struct SqlTableSchemaRelationship {
-    #[serde(rename = "Entry")]
-    pub entry: SqlTableSchemaRelationshipEntry,
+/// The `IDENTITY(seed, increment)` specification of a column
+#[derive(Debug, PartialEq)]
+pub struct SqlColumnIdentitySpecifier {
+    pub seed: i128,
+    pub increment: i128,
+    pub is_not_for_replication: bool,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(deny_unknown_fields)]
-pub struct SqlTableSchemaRelationshipEntry {
-    #[serde(rename = "References")]
-    pub references: SqlTableReference,
+impl TryFrom<&RawElement> for SqlColumnIdentitySpecifier {
+    type Error = anyhow::Error;
+
+    fn try_from(e: &RawElement) -> anyhow::Result<Self> {
+        Ok(SqlColumnIdentitySpecifier {
+            seed: e.parse_property("IdentitySeed")?.unwrap_or(1),
+            increment: e.parse_property("IdentityIncrement")?.unwrap_or(1),
+            is_not_for_replication: e.bool_property("IsIdentityNotForReplication", false),
+        })
+    }
 }
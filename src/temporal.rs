@@ -0,0 +1,213 @@
+//! Date and time values decoded from table data.
+//!
+//! Conversions to the `chrono` and `time` crates are available behind the
+//! cargo features of the same name.
+
+use std::fmt;
+
+/// A date between 0001-01-01 and 9999-12-31
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+/// A time of day, with the fractional seconds scale (0 to 7) of its column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub nanosecond: u32,
+    pub scale: u8,
+}
+
+/// A date and time without time zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+}
+
+/// A local date and time, with its offset from UTC in minutes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateTimeOffset {
+    pub local: DateTime,
+    pub offset_minutes: i16,
+}
+
+impl Date {
+    /// Builds a date from a number of days since 0001-01-01
+    pub fn from_days(days: u32) -> Date {
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days,
+        // shifted so that day 0 is 0001-01-01 instead of 1970-01-01
+        let z = i64::from(days) - 719_162 + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as u16;
+        Date { year, month, day }
+    }
+}
+
+impl Time {
+    /// Builds a time from a number of 10^-scale seconds since midnight.
+    /// `scale` must be at most 7, as checked by the BCP reader.
+    pub(crate) fn from_ticks(ticks: u64, scale: u8) -> Time {
+        let units_per_second = 10u64.pow(u32::from(scale));
+        let seconds = ticks / units_per_second;
+        let fraction = ticks % units_per_second;
+        Time {
+            hour: (seconds / 3600) as u8,
+            minute: (seconds / 60 % 60) as u8,
+            second: (seconds % 60) as u8,
+            nanosecond: (fraction * 10u64.pow(9 - u32::from(scale))) as u32,
+            scale,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Formats as `hh:mm:ss`, followed by as many fractional digits as the scale
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        if self.scale > 0 {
+            let fraction = self.nanosecond / 10u32.pow(9 - u32::from(self.scale.min(9)));
+            write!(f, ".{fraction:0width$}", width = usize::from(self.scale))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.date, self.time)
+    }
+}
+
+impl fmt::Display for DateTimeOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.offset_minutes < 0 { '-' } else { '+' };
+        let offset = self.offset_minutes.unsigned_abs();
+        write!(
+            f,
+            "{} {sign}{:02}:{:02}",
+            self.local,
+            offset / 60,
+            offset % 60
+        )
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_conversions {
+    use anyhow::{anyhow, Error, Result};
+
+    impl TryFrom<super::Date> for chrono::NaiveDate {
+        type Error = Error;
+
+        fn try_from(d: super::Date) -> Result<Self> {
+            chrono::NaiveDate::from_ymd_opt(d.year.into(), d.month.into(), d.day.into())
+                .ok_or_else(|| anyhow!("Invalid date {d}"))
+        }
+    }
+
+    impl TryFrom<super::Time> for chrono::NaiveTime {
+        type Error = Error;
+
+        fn try_from(t: super::Time) -> Result<Self> {
+            chrono::NaiveTime::from_hms_nano_opt(
+                t.hour.into(),
+                t.minute.into(),
+                t.second.into(),
+                t.nanosecond,
+            )
+            .ok_or_else(|| anyhow!("Invalid time {t}"))
+        }
+    }
+
+    impl TryFrom<super::DateTime> for chrono::NaiveDateTime {
+        type Error = Error;
+
+        fn try_from(dt: super::DateTime) -> Result<Self> {
+            Ok(chrono::NaiveDateTime::new(
+                dt.date.try_into()?,
+                dt.time.try_into()?,
+            ))
+        }
+    }
+
+    impl TryFrom<super::DateTimeOffset> for chrono::DateTime<chrono::FixedOffset> {
+        type Error = Error;
+
+        fn try_from(dto: super::DateTimeOffset) -> Result<Self> {
+            let offset = chrono::FixedOffset::east_opt(i32::from(dto.offset_minutes) * 60)
+                .ok_or_else(|| anyhow!("Invalid offset in {dto}"))?;
+            chrono::NaiveDateTime::try_from(dto.local)?
+                .and_local_timezone(offset)
+                .single()
+                .ok_or_else(|| anyhow!("Invalid datetimeoffset {dto}"))
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_conversions {
+    use anyhow::{Error, Result};
+
+    impl TryFrom<super::Date> for time::Date {
+        type Error = Error;
+
+        fn try_from(d: super::Date) -> Result<Self> {
+            Ok(time::Date::from_calendar_date(
+                d.year.into(),
+                time::Month::try_from(d.month)?,
+                d.day,
+            )?)
+        }
+    }
+
+    impl TryFrom<super::Time> for time::Time {
+        type Error = Error;
+
+        fn try_from(t: super::Time) -> Result<Self> {
+            Ok(time::Time::from_hms_nano(
+                t.hour,
+                t.minute,
+                t.second,
+                t.nanosecond,
+            )?)
+        }
+    }
+
+    impl TryFrom<super::DateTime> for time::PrimitiveDateTime {
+        type Error = Error;
+
+        fn try_from(dt: super::DateTime) -> Result<Self> {
+            Ok(time::PrimitiveDateTime::new(
+                dt.date.try_into()?,
+                dt.time.try_into()?,
+            ))
+        }
+    }
+
+    impl TryFrom<super::DateTimeOffset> for time::OffsetDateTime {
+        type Error = Error;
+
+        fn try_from(dto: super::DateTimeOffset) -> Result<Self> {
+            let offset = time::UtcOffset::from_whole_seconds(i32::from(dto.offset_minutes) * 60)?;
+            Ok(time::PrimitiveDateTime::try_from(dto.local)?.assume_offset(offset))
+        }
+    }
+}
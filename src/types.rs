@@ -0,0 +1,116 @@
+//! User-defined data types and table types.
+
+use anyhow::{anyhow, bail, Error, Result};
+
+use crate::constraint::{column_specifications, SqlIndexedColumnSpecification};
+use crate::element::RawElement;
+use crate::name::ObjectName;
+use crate::table::{ElementTypeSpecifier, SqlTableColumnElement};
+
+/// An alias type, i.e. `CREATE TYPE ... FROM base_type`
+#[derive(Debug, PartialEq)]
+pub struct SqlUserDefinedDataType {
+    pub name: ObjectName,
+    pub schema: String,
+    /// The built-in type with the facets of the alias
+    pub base_type: ElementTypeSpecifier,
+    pub is_nullable: bool,
+}
+
+/// A table type, i.e. `CREATE TYPE ... AS TABLE`
+#[derive(Debug, PartialEq)]
+pub struct SqlTableType {
+    pub name: ObjectName,
+    pub schema: String,
+    pub columns: Vec<SqlTableColumnElement>,
+    pub constraints: Vec<SqlTableTypeConstraint>,
+}
+
+/// An inline constraint of a table type. Unlike table constraints, these
+/// are elements of the table type and have no name.
+#[derive(Debug, PartialEq)]
+pub enum SqlTableTypeConstraint {
+    PrimaryKey {
+        column_specifications: Vec<SqlIndexedColumnSpecification>,
+        is_clustered: bool,
+    },
+    Unique {
+        column_specifications: Vec<SqlIndexedColumnSpecification>,
+        is_clustered: bool,
+    },
+    Check {
+        expression: String,
+    },
+    Default {
+        for_column: ObjectName,
+        expression: String,
+    },
+}
+
+impl TryFrom<&RawElement> for SqlUserDefinedDataType {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        // The facets are properties of the alias type itself, next to its
+        // `Type` relationship, just like on a `SqlTypeSpecifier`
+        Ok(SqlUserDefinedDataType {
+            schema: e.schema()?,
+            base_type: ElementTypeSpecifier::try_from(e)?,
+            is_nullable: e.bool_property("IsNullable", true),
+            name: e.object_name("User-defined data type")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlTableType {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        let columns = e
+            .elements("Columns")
+            .into_iter()
+            .map(SqlTableColumnElement::try_from)
+            .collect::<Result<_>>()?;
+        let constraints = e
+            .elements("Constraints")
+            .into_iter()
+            .map(SqlTableTypeConstraint::try_from)
+            .collect::<Result<_>>()?;
+
+        Ok(SqlTableType {
+            schema: e.schema()?,
+            columns,
+            constraints,
+            name: e.object_name("Table type")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlTableTypeConstraint {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        match e.ty.as_str() {
+            "SqlTableTypePrimaryKeyConstraint" => Ok(SqlTableTypeConstraint::PrimaryKey {
+                column_specifications: column_specifications(e)?,
+                is_clustered: e.bool_property("IsClustered", true),
+            }),
+            "SqlTableTypeUniqueConstraint" => Ok(SqlTableTypeConstraint::Unique {
+                column_specifications: column_specifications(e)?,
+                is_clustered: e.bool_property("IsClustered", false),
+            }),
+            "SqlTableTypeCheckConstraint" => Ok(SqlTableTypeConstraint::Check {
+                expression: e
+                    .property_value("CheckExpressionScript")
+                    .ok_or_else(|| anyhow!("Table type check constraint has no expression"))?,
+            }),
+            "SqlTableTypeDefaultConstraint" => Ok(SqlTableTypeConstraint::Default {
+                for_column: e.required_object_reference("ForColumn")?,
+                expression: e
+                    .property_value("DefaultExpressionScript")
+                    .ok_or_else(|| anyhow!("Table type default constraint has no expression"))?,
+            }),
+            ty => bail!("Unknown table type constraint {ty}"),
+        }
+    }
+}
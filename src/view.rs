@@ -0,0 +1,68 @@
+//! Views.
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::element::RawElement;
+use crate::name::ObjectName;
+
+#[derive(Debug, PartialEq)]
+pub struct SqlView {
+    pub name: ObjectName,
+    pub schema: String,
+    /// The `SELECT` statement of the view, as written in the source
+    pub query_script: String,
+    pub is_schema_bound: bool,
+    pub with_check_option: bool,
+    pub is_encrypted: bool,
+    /// The output columns, as resolved by DacFx from the query
+    pub columns: Vec<SqlViewColumn>,
+    /// Names of the objects used by the query
+    pub query_dependencies: Vec<ObjectName>,
+}
+
+/// A column of a view. DacFx writes them as `SqlComputedColumn` elements
+/// without an expression, only with the columns they come from.
+#[derive(Debug, PartialEq)]
+pub struct SqlViewColumn {
+    pub name: ObjectName,
+    pub dependencies: Vec<ObjectName>,
+}
+
+impl TryFrom<&RawElement> for SqlView {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        let columns = e
+            .elements("Columns")
+            .into_iter()
+            .map(SqlViewColumn::try_from)
+            .collect::<Result<_>>()?;
+
+        Ok(SqlView {
+            schema: e.schema()?,
+            query_script: e
+                .property_value("QueryScript")
+                .ok_or_else(|| anyhow!("{} has no QueryScript", e.display_name()))?,
+            is_schema_bound: e.bool_property("IsSchemaBound", false),
+            with_check_option: e.bool_property(
+                "WithCheckOption",
+                e.bool_property("IsWithCheckOption", false),
+            ),
+            is_encrypted: e.bool_property("IsEncrypted", false),
+            columns,
+            query_dependencies: e.object_references("QueryDependencies")?,
+            name: e.object_name("View")?,
+        })
+    }
+}
+
+impl TryFrom<&RawElement> for SqlViewColumn {
+    type Error = Error;
+
+    fn try_from(e: &RawElement) -> Result<Self> {
+        Ok(SqlViewColumn {
+            name: e.object_name("Column of a view")?,
+            dependencies: e.object_references("ExpressionDependencies")?,
+        })
+    }
+}